@@ -0,0 +1,39 @@
+// part of the Minesweeper game for the terminal
+// the cascade of a pick on large sparse boards, run it with `cargo bench`
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use minesweeper::{Board, BoardSize};
+
+/// the size of the boards, the largest the game creates is much larger
+const SIZE: u16 = 1000;
+
+/// a board with the bombs placed around the cell in the middle, no cell is revealed yet.
+/// The bombs of a played board are placed on a new one, so the pick doesn't place them
+fn create_board(mines: u32) -> Board {
+    let size = BoardSize::Custom { width: SIZE, height: SIZE, mines };
+    let mut played = Board::new(size);
+    played.set_seed(1);
+    played.pick_cell(SIZE as usize / 2, SIZE as usize / 2);
+    let mut board = Board::new(size);
+    board.set_bombs(&played.get_bombs());
+    board
+}
+
+/// one pick in the middle opens nearly the whole board, with fewer mines the cascade is larger
+fn flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flood_fill_1000x1000");
+    group.sample_size(10);
+    for mines in [1, 1_000, 10_000] {
+        let board = create_board(mines);
+        group.bench_function(format!("{}_mines", mines), |b| {
+            b.iter_batched_ref(
+                || board.clone(),
+                |board| board.pick_cell(SIZE as usize / 2, SIZE as usize / 2),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, flood_fill);
+criterion_main!(benches);
@@ -0,0 +1,173 @@
+/// Minesweeper solver
+/// plays many boards without a player and shows how difficult they are
+///
+/// Copyright (c) 2025 Markus Müller
+///
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+use minesweeper::game::board::{Board, BoardSize};
+use minesweeper::game::solver;
+use std::time::Instant;
+
+/// the help text for the command line
+const USAGE: &str = "Usage: minesweeper-solve [options] [<size>...]
+
+  <size>              small, medium, large or WIDTHxHEIGHTxMINES, all presets without a size
+  -n, --games <n>     the games to play for every size, 100 without it
+  --seed <number>     the seed of the first game, the next games count up from it
+  --no-guess          create boards, that can be solved without a guess
+  --unsafe-start      the neighbours of the first cell can have mines
+  --json              print the results as JSON instead of a table";
+
+/// the options of the command line
+struct Options {
+    sizes: Vec<BoardSize>,
+    games: u32,
+    seed: u64,
+    no_guess: bool,
+    safe_neighbours: bool,
+    json: bool,
+}
+
+/// the results of all games of one board size
+struct Results {
+    width: usize,
+    height: usize,
+    mines: u32,
+    games: u32,
+    won: u32,
+    guesses: u64,
+    seconds: f64,
+}
+
+impl Results {
+
+    fn get_win_rate(&self) -> f64 {
+        self.won as f64 * 100.0 / self.games.max(1) as f64
+    }
+
+    fn get_average_guesses(&self) -> f64 {
+        self.guesses as f64 / self.games.max(1) as f64
+    }
+
+    fn get_average_ms(&self) -> f64 {
+        self.seconds * 1_000.0 / self.games.max(1) as f64
+    }
+
+}
+
+fn main() -> Result<(), i32> {
+
+    let options = read_args(std::env::args()).map_err(|msg| {
+        eprintln!("{}", msg);
+        1
+    })?;
+
+    let results: Vec<Results> = options.sizes.iter().map(|&size| play(&options, size)).collect();
+    if options.json {
+        print_json(&options, &results);
+    } else {
+        print_table(&options, &results);
+    }
+    Ok(())
+}
+
+/// read the command line, returns an error message for invalid arguments
+fn read_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        sizes: Vec::new(),
+        games: 100,
+        seed: rand::random(),
+        no_guess: false,
+        safe_neighbours: true,
+        json: false,
+    };
+    let mut args = args.skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--games" => {
+                let value = args.next().ok_or("--games needs a number")?;
+                options.games = value.parse().map_err(|_| format!("invalid number of games: {}", value))?;
+            }
+            "--seed" => {
+                let value = args.next().ok_or("--seed needs a number")?;
+                options.seed = value.parse().map_err(|_| format!("invalid seed: {}", value))?;
+            }
+            "--no-guess" => options.no_guess = true,
+            "--unsafe-start" => options.safe_neighbours = false,
+            "--json" => options.json = true,
+            "-h" | "--help" => return Err(String::from(USAGE)),
+            size => options.sizes.push(parse_size(size).ok_or_else(|| format!("unknown argument: {}\n{}", arg, USAGE))?),
+        }
+    }
+    if options.sizes.is_empty() {
+        options.sizes = vec![BoardSize::Small, BoardSize::Medium, BoardSize::Large];
+    }
+    Ok(options)
+}
+
+/// get the board size of a preset name or of WIDTHxHEIGHTxMINES
+fn parse_size(text: &str) -> Option<BoardSize> {
+    match text {
+        "small" => Some(BoardSize::Small),
+        "medium" => Some(BoardSize::Medium),
+        "large" => Some(BoardSize::Large),
+        _ => {
+            let mut values = text.split('x');
+            let size = BoardSize::Custom {
+                width: values.next()?.parse().ok()?,
+                height: values.next()?.parse().ok()?,
+                mines: values.next()?.parse().ok()?,
+            };
+            if values.next().is_some() { None } else { Some(size) }
+        }
+    }
+}
+
+/// play all games of a board size, the first pick is in the middle of the board like in the game
+fn play(options: &Options, size: BoardSize) -> Results {
+    let (width, height, mines) = size.get_dimension();
+    let mut results = Results { width, height, mines, games: options.games, won: 0, guesses: 0, seconds: 0.0 };
+    for game in 0..options.games {
+        let mut board = Board::new(size);
+        board.set_seed(options.seed.wrapping_add(game as u64));
+        board.set_no_guess(options.no_guess);
+        board.set_safe_neighbours(options.safe_neighbours);
+        let start = Instant::now();
+        let result = solver::autoplay(&mut board, width / 2, height / 2);
+        results.seconds += start.elapsed().as_secs_f64();
+        results.guesses += result.guesses as u64;
+        if result.won {
+            results.won += 1;
+        }
+    }
+    results
+}
+
+/// print a line for every board size
+fn print_table(options: &Options, results: &[Results]) {
+    println!("Seed {}, {} games per board{}", options.seed, options.games, if options.no_guess { ", no guess" } else { "" });
+    println!();
+    println!("{:<16} {:>7} {:>7} {:>9} {:>8} {:>10}", "Board", "Games", "Won", "Win rate", "Guesses", "ms/game");
+    for r in results {
+        let board = format!("{}x{} {} mines", r.width, r.height, r.mines);
+        println!("{:<16} {:>7} {:>7} {:>8.1}% {:>8.2} {:>10.2}",
+            board, r.games, r.won, r.get_win_rate(), r.get_average_guesses(), r.get_average_ms());
+    }
+}
+
+/// print the results as a JSON object, the boards are in an array
+fn print_json(options: &Options, results: &[Results]) {
+    let boards: Vec<String> = results.iter().map(|r| format!(
+        "{{\"width\":{},\"height\":{},\"mines\":{},\"games\":{},\"won\":{},\"win_rate\":{:.2},\"average_guesses\":{:.3},\"average_ms\":{:.3}}}",
+        r.width, r.height, r.mines, r.games, r.won, r.get_win_rate(), r.get_average_guesses(), r.get_average_ms()
+    )).collect();
+    println!("{{\"seed\":{},\"games\":{},\"no_guess\":{},\"safe_neighbours\":{},\"boards\":[{}]}}",
+        options.seed, options.games, options.no_guess, options.safe_neighbours, boards.join(","));
+}
@@ -1,110 +1,116 @@
 // part of the Minesweeper game for the terminal
-mod board;
-mod cell;
+pub mod board;
+pub mod cell;
+// without the frontend the changes of the moves are never undone
+#[cfg_attr(not(feature = "terminal"), allow(dead_code))]
+mod history;
+pub mod position;
+pub mod solver;
+
+// the terminal frontend, the library can be used without it
+#[cfg(feature = "terminal")]
 mod dimension;
+#[cfg(feature = "terminal")]
 mod game_struct;
+#[cfg(feature = "terminal")]
 mod input;
-mod position;
+#[cfg(feature = "terminal")]
+mod replay;
+#[cfg(feature = "terminal")]
+mod savegame;
+#[cfg(feature = "terminal")]
+mod scores;
+#[cfg(feature = "terminal")]
 mod screens;
-
+#[cfg(feature = "terminal")]
+mod settings;
+#[cfg(feature = "terminal")]
+mod stats;
+#[cfg(feature = "terminal")]
+mod storage;
+#[cfg(feature = "terminal")]
+mod viewport;
+
+#[cfg(feature = "terminal")]
 use crate::game::{
     game_struct::{Game,GameState},
     input::user_input,
-    screens::*
+    screens::draw_screen,
+    settings::Settings
 };
+#[cfg(feature = "terminal")]
 use crate::term::Term;
 
 /// the name of the game ;-)
+#[cfg(feature = "terminal")]
 const GAME_NAME: &str = "M I N E S W E E P E R";
 
 /// the main function to process the game and the user input
+#[cfg(feature = "terminal")]
 pub fn run() -> Result<(), i32> {
 
-    // create handle to Stdout and init crossterm-terminal
-    let mut term = Term::new();
-    term.enable_raw_mode();
-    term.enable_focus_events();
-    term.enable_mouse_events();
-    term.hide_cursor();
-    term.cls();
-
-    // create game struct and draw the start screen, the player select the size of the board here
-    let mut game = Game::new(draw_start_screen(&mut term));
-    let mut gfx = game.get_board_gfx();
-
-    // thats the main function to draw the board to the terminal
-    draw_main_screen(&mut term, &game, gfx);
+    // read the command line before the terminal is switched to raw mode
+    let settings = Settings::from_args(std::env::args()).map_err(|msg| {
+        eprintln!("{}", msg);
+        1
+    })?;
 
-    // init the timer and print it to the screen, this is independed of the main screen
-    let mut timer_start = std::time::Instant::now();
-    term.print_xy(game.get_seconds_x(), game.get_seconds_y(), &game.get_seconds_text());
-    term.update();
+    // create handle to Stdout and init crossterm-terminal,
+    // the terminal is restored when term is dropped, even after a panic
+    let mut term = Term::new().map_err(|err| {
+        eprintln!("Error: {}", err);
+        1
+    })?;
 
-    game.set_gamestate(GameState::Running);
+    // create game struct, the game begins with the start screen, the player select the size of the board there
+    let mut game = Game::new(settings);
 
     // main game loop
     loop {
 
-        // get the user input here
+        // get the user input here, the screen on top of the stack processes it
         user_input(&mut game);
 
-        if game.check_win_condition() && game.get_gamestate() == GameState::Running {
-            game.set_gamestate(GameState::Win);
-            game.pause = true;
+        // a signal like SIGTERM quits the game too
+        if term.is_quit_requested() {
+            game.set_gamestate(GameState::GiveUp);
+        }
+
+        if game.get_gamestate() == GameState::GiveUp {
+            break;
         }
 
-        // update timer only, if the game has the focus
-        if !game.pause {
+        // update timer only, if the board is shown and the game has the focus,
+        // the time is printed independed of the other screen updates
+        if game.is_timer_running() {
             let now = std::time::Instant::now();
-            if now.duration_since(timer_start).as_millis() >= 1_000 {
+            if now.duration_since(game.timer_start).as_millis() >= 1_000 {
                 game.seconds += 1;
-                timer_start = now;
+                game.timer_start = now;
             }
             term.print_xy(game.get_seconds_x(), game.get_seconds_y(), &game.get_seconds_text());
             term.update();
+        } else {
+            game.timer_start = std::time::Instant::now();
         }
 
-        // if the game screen should be updated, repaint the whole terminal
-        if game.update {
-            // select here the board to view
-            gfx = game.get_board_gfx();
-            draw_main_screen(&mut term, &game, gfx);
-            game.update = false;
+        // the replay plays the moves with its own clock
+        if game.get_gamestate() == GameState::Replay {
+            game.update_playback();
         }
 
-        // now select here the ending condition
-        match game.get_gamestate() {
-
-            GameState::GiveUp => break,
-
-            GameState::Loose | GameState::Win => {
-                term.print_xy(game.get_seconds_x(), game.get_seconds_y(), &game.get_seconds_text());
-                draw_end_screen(&mut term, &game);
-                game.update = false;
-            }
-
-            GameState::Start => {
-                game.pause = false;
-                game.set_gamestate(GameState::Running);
-                game.seconds = 0;
-                game.reset_board();
-                game.update = true;
-            }
-
-            _ => {}
+        // if the screen should be updated, repaint the whole terminal
+        if game.update {
+            game.update_analysis();
+            term.hide_cursor();
+            draw_screen(&mut term, &game);
+            game.update = false;
         }
 
     }
 
     // clean up the crossterm terminal functions
-    term.update();
-    term.cls();
-    term.show_cursor();
-
-    term.disable_mouse_events();
-    term.disable_focus_events();
-
-    term.disable_raw_mode();
+    drop(term);
 
     // and leave the game
     Ok(())
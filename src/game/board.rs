@@ -1,9 +1,28 @@
 // part of the Minesweeper game for the terminal
-use rand::random_range;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use crate::game::cell::{Cell, CellMarker};
+use crate::game::history::{CellChange, Delta};
+use crate::game::position::Position;
+use crate::game::solver;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
-const MAX_BOARD_WIDTH: usize = 30;
-const MAX_BOARD_HEIGHT: usize = 30;
+pub const MAX_BOARD_WIDTH: usize = u16::MAX as usize;
+pub const MAX_BOARD_HEIGHT: usize = u16::MAX as usize;
+pub const MIN_BOARD_WIDTH: usize = 2;
+pub const MIN_BOARD_HEIGHT: usize = 2;
+/// The cells of a board must fit into the memory, a wide board can't be as high
+pub const MAX_BOARD_CELLS: usize = 10_000_000;
+
+/// The cell under the keyboard cursor is shown in reverse video
+#[cfg(feature = "terminal")]
+const CURSOR_START: &str = "\x1b[7m";
+#[cfg(feature = "terminal")]
+const CURSOR_END: &str = "\x1b[27m";
+
+/// The time to find a board without guesses, after that the last generated board is used
+const NO_GUESS_TIME_BUDGET: Duration = Duration::from_secs(3);
 
 /// An enum to describe the size of the game board
 /// The size of the board is the difficulty of the game too
@@ -12,48 +31,251 @@ pub enum BoardSize {
     #[default]
     Small,
     Medium,
-    Large
+    Large,
+    Custom { width: u16, height: u16, mines: u32 }
+}
+
+impl BoardSize {
+
+    /// Get the width, height and the number of mines for this board size,
+    /// the values of a custom board are clamped to the valid range, the height is cut
+    /// if the board has more than the maximum number of cells
+    pub fn get_dimension(&self) -> (usize, usize, u32) {
+        match *self {
+            BoardSize::Small => BoardSize::preset(10, 10),
+            BoardSize::Medium => BoardSize::preset(20, 20),
+            BoardSize::Large => BoardSize::preset(30, 30),
+            BoardSize::Custom { width, height, mines } => {
+                let w = (width as usize).clamp(MIN_BOARD_WIDTH, MAX_BOARD_WIDTH);
+                let h = (height as usize).clamp(MIN_BOARD_HEIGHT, BoardSize::max_height(w));
+                (w, h, mines.clamp(1, BoardSize::max_mines(w, h)))
+            }
+        }
+    }
+
+    /// The presets have a mine on every 10th cell
+    fn preset(w: usize, h: usize) -> (usize, usize, u32) {
+        (w, h, (w * h / 10) as u32)
+    }
+
+    /// The maximum height of a board with the given width
+    pub fn max_height(width: usize) -> usize {
+        MAX_BOARD_HEIGHT.min(MAX_BOARD_CELLS / width.max(1))
+    }
+
+    /// The maximum number of mines on a board, at least one cell must be free
+    pub fn max_mines(width: usize, height: usize) -> u32 {
+        (width * height - 1).min(u32::MAX as usize) as u32
+    }
+
+}
+
+/// The state of the game on a board
+#[derive(Copy,Clone,Debug,Default,PartialEq)]
+pub enum GameState {
+    /// no cell is picked yet, the bombs are placed with the first pick
+    #[default]
+    Ready,
+    Running,
+    Won,
+    Lost,
+}
+
+/// The result of a move: the cells, that changed, and the state of the game after the move
+#[derive(Clone,Debug,Default,PartialEq)]
+pub struct Outcome {
+    pub changed: Vec<Position>,
+    pub state: GameState,
+}
+
+impl Outcome {
+
+    /// check if the move uncovered a bomb
+    pub fn is_exploded(&self) -> bool {
+        self.state == GameState::Lost
+    }
+
+}
+
+/// The result of a picked cell
+#[derive(Clone,Debug,PartialEq)]
+pub enum RevealResult {
+    /// the cell is revealed already, nothing changed
+    AlreadyRevealed,
+    /// a flag or a question mark protects the cell
+    Flagged,
+    /// the cell is a bomb
+    Exploded,
+    /// the opened cells, the picked cell comes first and the cascade of the empty cells follows
+    Opened(Vec<Position>),
+    /// the cell is not on the board
+    OutOfBounds,
+}
+
+impl RevealResult {
+
+    /// check if the pick uncovered a bomb
+    pub fn is_exploded(&self) -> bool {
+        *self == RevealResult::Exploded
+    }
+
 }
 
 /// A struct to describe the game board
-/// The descision goes here to an array instead of a rust vector, it's easier to handle
-#[derive(Copy,Clone,Debug)]
+/// The cells are stored row by row in a vector, so the size of the board
+/// is only limited by the memory. Use `index()` to get the position of a cell.
+/// The changes of the last move are kept in a delta, so that the frontend can undo it.
+#[derive(Clone,Debug)]
 pub struct Board {
     width: usize,
     height: usize,
-    max_bombs: u16,
-    cell: [[Cell; MAX_BOARD_HEIGHT]; MAX_BOARD_WIDTH]
+    max_bombs: u32,
+    safe_neighbours: bool,
+    no_guess: bool,
+    guess_free: bool,
+    flag_all: bool,
+    seed: u64,
+    start: Option<(usize, usize)>,
+    populated: bool,
+    cells: Vec<Cell>,
+    delta: Delta,
 }
 
 impl Board {
 
     /// Creates a new game board, must be done once at program start
-    pub fn new(board_size: BoardSize, difficult: bool) -> Board {
-        let (w,h) = match board_size {
-            BoardSize::Small => (MAX_BOARD_WIDTH-20,MAX_BOARD_HEIGHT-20),
-            BoardSize::Medium => (MAX_BOARD_WIDTH-10,MAX_BOARD_HEIGHT-10),
-            BoardSize::Large => (MAX_BOARD_WIDTH,MAX_BOARD_HEIGHT),
-        };
-        let mut new_board = Board {
+    /// The bombs are placed with the first picked cell, so the first move is always safe
+    pub fn new(board_size: BoardSize) -> Board {
+        let (w,h,mines) = board_size.get_dimension();
+        Board {
             width: w,
             height: h,
-            max_bombs: if difficult {
-                (w * h / 5) as u16
-            } else {
-                (w * h / 10) as u16
-            },
-            cell: [[Cell::new(); MAX_BOARD_HEIGHT]; MAX_BOARD_WIDTH]
-        };
-        new_board.populate_cells();
-        new_board
-    }
-
-    /// Clear all cells of the game board and reset them to default values
+            max_bombs: mines,
+            safe_neighbours: true,
+            no_guess: false,
+            guess_free: false,
+            flag_all: false,
+            seed: rand::random(),
+            start: None,
+            populated: false,
+            cells: vec![Cell::new(); w * h],
+            delta: Delta::default(),
+        }
+    }
+
+    /// Clear all cells of the game board and reset them to default values,
+    /// the bombs will be placed again with the next picked cell
     pub fn clear(&mut self) {
-        for y in 0..MAX_BOARD_HEIGHT {
-            for x in 0..MAX_BOARD_WIDTH {
-                self.cell[x][y].clear();
-            }
+        for cell in self.cells.iter_mut() {
+            cell.clear();
+        }
+        self.populated = false;
+        self.guess_free = false;
+        self.start = None;
+        self.delta = Delta::default();
+    }
+
+    /// If set, the 8 neighbours of the first picked cell are free of bombs too
+    pub fn set_safe_neighbours(&mut self, state: bool) {
+        self.safe_neighbours = state;
+    }
+
+    /// Set the seed for the bombs, the same seed and the same first picked cell
+    /// always create the same board, the generator doesn't change with the version of rand
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Get the seed of the board, in the no guess mode it's the seed of the board that was used
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the first picked cell, the bombs were placed around it
+    pub fn get_start(&self) -> Option<(usize, usize)> {
+        self.start
+    }
+
+    /// Set the first picked cell of a board from a file
+    pub fn set_start(&mut self, x: usize, y: usize) {
+        self.start = Some((x, y));
+    }
+
+    /// If set, the user must flag all bombs to win the game, like in the early versions of this game
+    pub fn set_flag_all(&mut self, state: bool) {
+        self.flag_all = state;
+    }
+
+    /// If set, the bombs are placed so that the board can be solved without guessing
+    pub fn set_no_guess(&mut self, state: bool) {
+        self.no_guess = state;
+    }
+
+    /// Check if the solver cleared the board without a guess, in the no guess mode
+    /// this is FALSE only if the time budget was used up before such a board was found
+    pub fn is_guess_free(&self) -> bool {
+        self.guess_free
+    }
+
+    /// Get the index of a cell in the vector, the position must be valid
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Get a reference to the cell at the given position
+    fn cell(&self, x: usize, y: usize) -> &Cell {
+        &self.cells[self.index(x, y)]
+    }
+
+    /// Get a mutable reference to the cell at the given position
+    fn cell_mut(&mut self, x: usize, y: usize) -> &mut Cell {
+        let index = self.index(x, y);
+        &mut self.cells[index]
+    }
+
+    /// Reveal a cell and keep the change for the undo
+    fn uncover(&mut self, x: usize, y: usize) {
+        let index = self.index(x, y);
+        let before = self.cells[index];
+        self.cells[index].set_revealed(true);
+        if before.is_bomb() {
+            self.delta.exploded = true;
+        }
+        self.delta.changes.push(CellChange { index, before, after: self.cells[index] });
+    }
+
+    /// Set the marker of a cell and keep the change for the undo
+    fn set_marker(&mut self, x: usize, y: usize, marker: CellMarker) {
+        let index = self.index(x, y);
+        let before = self.cells[index];
+        self.cells[index].set_state(marker);
+        self.delta.changes.push(CellChange { index, before, after: self.cells[index] });
+    }
+
+    /// Start a new move, the changes of the last move are dropped
+    fn begin_move(&mut self) {
+        self.delta = Delta::default();
+    }
+
+    /// Get the changes of the last move, the move can be undone with them
+    #[cfg(feature = "terminal")]
+    pub(crate) fn take_delta(&mut self) -> Delta {
+        std::mem::take(&mut self.delta)
+    }
+
+    /// Undo the changes of a move, the bombs stay on the board, even if the move placed them
+    #[cfg(feature = "terminal")]
+    pub(crate) fn undo(&mut self, delta: &Delta) {
+        for change in delta.changes.iter().rev() {
+            self.cells[change.index] = change.before;
+        }
+    }
+
+    /// Do the changes of an undone move again
+    #[cfg(feature = "terminal")]
+    pub(crate) fn redo(&mut self, delta: &Delta) {
+        for change in &delta.changes {
+            self.cells[change.index] = change.after;
         }
     }
 
@@ -78,7 +300,7 @@ impl Board {
                 if self.is_cell_valid(index_x, index_y) {
                     let new_x = index_x as usize;
                     let new_y = index_y as usize;
-                    if self.cell[new_x][new_y].is_bomb() {
+                    if self.cell(new_x, new_y).is_bomb() {
                         bombs_around += 1;
                     }
                 }
@@ -87,36 +309,158 @@ impl Board {
         bombs_around
     }
 
-    /// Populate all cells of the playfield, with bombs or leave it empty
+    /// Count the bombs around every cell without a bomb
+    fn count_all_bombs_around(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.cell(x, y).is_bomb() { continue; }
+                let bombs_around = self.count_bombs_around(x, y);
+                self.cell_mut(x, y).set_bombs_around(bombs_around);
+            }
+        }
+    }
+
+    /// Count the valid cells around the given cell
+    fn count_cells_around(&self, x_pos: usize, y_pos: usize) -> usize {
+        let mut cells_around = 0;
+        for y in -1..=1 {
+            for x in -1..=1 {
+                if x == 0 && y == 0 { continue; }
+                if self.is_cell_valid(x_pos as isize + x, y_pos as isize + y) {
+                    cells_around += 1;
+                }
+            }
+        }
+        cells_around
+    }
+
+    /// Populate all cells of the playfield, with bombs or leave it empty,
+    /// the given cell is never a bomb and if there is enough space its neighbours too.
+    /// In the no guess mode the bombs are placed again, until the solver clears the board
+    /// from the given cell or the time budget is used, then the last board may need a guess.
     /// For any cell the bombs around will be counted too
-    pub fn populate_cells(&mut self) {
-        let mut bombs: u16 = 0;
+    fn populate_cells(&mut self, safe_x: usize, safe_y: usize) {
+        let mut safe_radius = 0;
+        // without an opening area the solver has nothing to start with
+        if self.safe_neighbours || self.no_guess {
+            let safe_cells = self.count_cells_around(safe_x, safe_y) + 1;
+            if self.width * self.height - safe_cells >= self.max_bombs as usize {
+                safe_radius = 1;
+            }
+        }
+        let deadline = Instant::now() + NO_GUESS_TIME_BUDGET;
+        self.guess_free = false;
+        self.start = Some((safe_x, safe_y));
+        loop {
+            let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+            self.place_bombs(&mut rng, safe_x, safe_y, safe_radius);
+            if !self.no_guess || Instant::now() >= deadline {
+                break;
+            }
+            if solver::is_solvable(self, safe_x, safe_y, deadline) {
+                self.guess_free = true;
+                break;
+            }
+            // every try has its own seed, so the seed of the board creates it again without the search
+            self.seed = rng.random();
+            for cell in self.cells.iter_mut() {
+                cell.clear_content();
+            }
+        }
+        self.populated = true;
+    }
+
+    /// Place the bombs randomly outside of the safe area and count the bombs around
+    fn place_bombs(&mut self, rng: &mut ChaCha8Rng, safe_x: usize, safe_y: usize, safe_radius: usize) {
+        let mut bombs: u32 = 0;
         while bombs < self.max_bombs {
-            let x: usize = random_range(0..self.width);
-            let y: usize = random_range(0..self.height);
-            if !self.cell[x][y].is_bomb() {
-                self.cell[x][y].set_content_bomb();
+            let x: usize = rng.random_range(0..self.width);
+            let y: usize = rng.random_range(0..self.height);
+            if x.abs_diff(safe_x) <= safe_radius && y.abs_diff(safe_y) <= safe_radius { continue; }
+            if !self.cell(x, y).is_bomb() {
+                self.cell_mut(x, y).set_content_bomb();
                 bombs += 1;
             }
         }
-        for y in 0..self.height {
-            for x in 0..self.width {
-                //self.cell[x][y].pick();
-                if self.cell[x][y].is_bomb() { continue; }
-                let bombs_around = self.count_bombs_around(x, y);
-                self.cell[x][y].set_bombs_around(bombs_around);
+        self.count_all_bombs_around();
+    }
+
+    /// Get the cells as text for the save file, a line for every row
+    pub fn get_cells_text(&self) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width) {
+            text.extend(row.iter().map(|cell| cell.get_char()));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Set the cells from the text of a save file, the bombs around are counted again.
+    /// Returns FALSE if the text does not fit the size and the bombs of the board
+    pub fn set_cells_text(&mut self, text: &str) -> bool {
+        let mut cells = Vec::with_capacity(self.width * self.height);
+        for line in text.lines() {
+            if line.chars().count() != self.width {
+                return false;
             }
+            for c in line.chars() {
+                let Some(cell) = Cell::from_char(c) else { return false; };
+                cells.push(cell);
+            }
+        }
+        if cells.len() != self.width * self.height
+            || cells.iter().filter(|cell| cell.is_bomb()).count() != self.max_bombs as usize {
+            return false;
         }
+        self.cells = cells;
+        self.count_all_bombs_around();
+        self.populated = true;
+        true
+    }
+
+    /// Get the indexes of the bombs, a line of the replay file
+    pub fn get_bombs(&self) -> Vec<usize> {
+        (0..self.cells.len()).filter(|&i| self.cells[i].is_bomb()).collect()
+    }
+
+    /// Place the bombs of a replay file, the board must be empty.
+    /// Returns FALSE if the bombs are placed already or if they don't fit the board
+    pub fn set_bombs(&mut self, bombs: &[usize]) -> bool {
+        if self.populated || bombs.len() != self.max_bombs as usize || bombs.iter().any(|&index| index >= self.cells.len()) {
+            return false;
+        }
+        for &index in bombs {
+            self.cells[index].set_content_bomb();
+        }
+        if self.cells.iter().filter(|cell| cell.is_bomb()).count() != bombs.len() {
+            self.clear();
+            return false;
+        }
+        self.count_all_bombs_around();
+        self.populated = true;
+        true
     }
 
     /// Get a String of the cell
+    #[allow(unused)]
     pub fn print_cell(&self, x: usize, y: usize) -> String {
         if self.is_cell_valid(x as isize, y as isize) {
-            return format!("{}", self.cell[x][y]);
+            return format!("{}", self.cell(x, y));
         }
         String::from("No valid cell")
     }
 
+    /// Get a cell of the game board, it panics if the position is outside of the board
+    pub fn get_cell(&self, x: usize, y: usize) -> &Cell {
+        assert!(x < self.width && y < self.height, "cell {},{} is outside of the board", x, y);
+        self.cell(x, y)
+    }
+
+    /// Get the number of bombs on the game board
+    pub fn get_max_bombs(&self) -> u32 {
+        self.max_bombs
+    }
+
     /// Get the width of the game board
     pub fn get_width(&self) -> u16 {
         self.width as u16
@@ -127,15 +471,29 @@ impl Board {
         self.height as u16
     }
 
-    /// Get the whole game board,
-    /// one element in the vector is one horizontal line of the game board.
+    /// Get the visible area of the game board, the cell under the cursor is highlighted,
+    /// the other cells can be tinted with a background color.
+    /// One element in the vector is one horizontal line of the area.
     /// Use this function to view the game board to the user
-    pub fn get_gfx(&self) -> Vec<String> {
-        let mut gfx_vec = Vec::with_capacity(self.height);
-        for y in 0..self.height {
+    #[cfg(feature = "terminal")]
+    pub(crate) fn get_gfx(&self, x_pos: usize, y_pos: usize, width: usize, height: usize, cursor: Option<(usize,usize)>, background: impl Fn(usize, usize) -> Option<String>) -> Vec<String> {
+        let x_end = (x_pos + width).min(self.width);
+        let y_end = (y_pos + height).min(self.height);
+        let mut gfx_vec = Vec::with_capacity(height);
+        for y in y_pos..y_end {
             let mut line = String::new();
-            for x in 0..self.width {
-                line += self.cell[x][y].get_gfx().as_str();
+            for x in x_pos..x_end {
+                let gfx = match background(x, y) {
+                    Some(background) => self.cell(x, y).get_gfx_on(&background),
+                    None => self.cell(x, y).get_gfx(),
+                };
+                if cursor == Some((x, y)) {
+                    line += CURSOR_START;
+                    line += gfx.as_str();
+                    line += CURSOR_END;
+                } else {
+                    line += gfx.as_str();
+                }
             }
             line += "\x1b[0m";
             gfx_vec.push(line);
@@ -143,16 +501,20 @@ impl Board {
         gfx_vec
     }
 
-    /// Get the whole game board for DEBUG view
-    pub fn dbg_gfx(&mut self) -> Vec<String> {
-        let mut gfx_vec = Vec::with_capacity(self.height);
-        for y in 0..self.height {
+    /// Get the visible area of the game board for DEBUG view
+    #[cfg(feature = "terminal")]
+    pub(crate) fn dbg_gfx(&self, x_pos: usize, y_pos: usize, width: usize, height: usize, cursor: Option<(usize,usize)>) -> Vec<String> {
+        let x_end = (x_pos + width).min(self.width);
+        let y_end = (y_pos + height).min(self.height);
+        let mut gfx_vec = Vec::with_capacity(height);
+        for y in y_pos..y_end {
             let mut line = String::new();
-            for x in 0..self.width {
-                let state = self.cell[x][y].is_revealed();
-                self.cell[x][y].set_revealed(true);
-                line += self.cell[x][y].get_gfx().as_str();
-                self.cell[x][y].set_revealed(state);
+            for x in x_pos..x_end {
+                let mut cell = *self.cell(x, y);
+                cell.set_revealed(true);
+                if cursor == Some((x, y)) { line += CURSOR_START; }
+                line += cell.get_gfx().as_str();
+                if cursor == Some((x, y)) { line += CURSOR_END; }
             }
             line += "\x1b[0m";
             gfx_vec.push(line);
@@ -161,85 +523,348 @@ impl Board {
     }
 
     /// Pick a cell and process with the game logic,
-    /// returns TRUE if the user clicked/uncovered a bomb otherwise FALSE
-    pub fn pick_cell(&mut self, cell_x: usize, cell_y: usize) -> bool {
+    /// returns the opened cells or why nothing was opened
+    pub fn pick_cell(&mut self, cell_x: usize, cell_y: usize) -> RevealResult {
+        self.begin_move();
+        self.open_cell(cell_x, cell_y)
+    }
+
+    /// Open a cell as a part of a move, a chord opens some of them
+    fn open_cell(&mut self, cell_x: usize, cell_y: usize) -> RevealResult {
 
         if !self.is_cell_valid(cell_x as isize, cell_y as isize) {
-            return false;
+            return RevealResult::OutOfBounds;
+        }
+
+        if self.cell(cell_x, cell_y).get_state() != CellMarker::None {
+            return RevealResult::Flagged;
+        }
+
+        // the first picked cell places the bombs
+        if !self.populated {
+            self.populate_cells(cell_x, cell_y);
+        }
+
+        if self.cell(cell_x, cell_y).is_revealed() {
+            return RevealResult::AlreadyRevealed;
+        }
+
+        self.uncover(cell_x, cell_y);
+        if self.cell(cell_x, cell_y).is_bomb() {
+            return RevealResult::Exploded;
+        }
+
+        let mut opened = vec![Position::new(cell_x as u16, cell_y as u16)];
+        if self.cell(cell_x, cell_y).get_bombs_around() == 0 {
+            self.open_around(cell_x, cell_y, &mut opened);
         }
+        RevealResult::Opened(opened)
 
-        if self.cell[cell_x][cell_y].get_state() != CellMarker::None {
+    }
+
+    /// Open the cells around an empty cell, the empty cells among them open their neighbours too.
+    /// The fill goes breadth first with a queue, so a large board can't overflow the stack.
+    /// A cell is revealed when it is queued, so the revealed cells are the visited set
+    /// and every cell is queued only once. Cells with a marker stay covered
+    fn open_around(&mut self, cell_x: usize, cell_y: usize, opened: &mut Vec<Position>) {
+        let mut queue = VecDeque::from([(cell_x, cell_y)]);
+        while let Some((x_pos, y_pos)) = queue.pop_front() {
+            for y in y_pos.saturating_sub(1)..=(y_pos + 1).min(self.height - 1) {
+                for x in x_pos.saturating_sub(1)..=(x_pos + 1).min(self.width - 1) {
+                    let cell = self.cell(x, y);
+                    if cell.is_revealed() || cell.get_state() != CellMarker::None { continue }
+                    self.uncover(x, y);
+                    opened.push(Position::new(x as u16, y as u16));
+                    if self.cell(x, y).get_bombs_around() == 0 {
+                        queue.push_back((x, y));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Chord a revealed number: if the flags around match the number,
+    /// all other cells around are picked, a question mark is removed before.
+    /// Returns TRUE if a wrong flag let the user uncover a bomb otherwise FALSE
+    fn chord_cell(&mut self, cell_x: usize, cell_y: usize) -> bool {
+
+        if !self.is_cell_valid(cell_x as isize, cell_y as isize) {
             return false;
         }
 
-        if !self.cell[cell_x][cell_y].is_revealed() {
+        let bombs_around = self.cell(cell_x, cell_y).get_bombs_around();
+        if !self.cell(cell_x, cell_y).is_revealed() || bombs_around == 0 {
+            return false;
+        }
 
-            self.cell[cell_x][cell_y].set_revealed(true);
+        if self.count_flags_around(cell_x, cell_y) != bombs_around {
+            return false;
+        }
 
-            if self.cell[cell_x][cell_y].is_bomb() {
-                return true;
-            } else {
-                if self.cell[cell_x][cell_y].get_bombs_around() > 0 {
-                    return false;
+        let mut exploded = false;
+        for y in -1..=1 {
+            for x in -1..=1 {
+                if x == 0 && y == 0 { continue }
+                let new_x: isize = cell_x as isize + x;
+                let new_y: isize = cell_y as isize + y;
+                if !self.is_cell_valid(new_x, new_y) { continue }
+                let (new_x, new_y) = (new_x as usize, new_y as usize);
+                match self.cell(new_x, new_y).get_state() {
+                    CellMarker::HasBomb => continue,
+                    CellMarker::GuessBomb => self.set_marker(new_x, new_y, CellMarker::None),
+                    CellMarker::None => {}
                 }
-                for y in -1..=1 {
-                    for x in -1..=1 {
-                        if x == 0 && y == 0 { continue }
-                        let new_x: isize = cell_x as isize + x;
-                        let new_y: isize = cell_y as isize + y;
-                        if self.is_cell_valid(new_x, new_y) {
-                            self.pick_cell(new_x as usize, new_y as usize);
-                        }
-                    }
+                if self.open_cell(new_x, new_y).is_exploded() {
+                    exploded = true;
                 }
             }
         }
 
-        false
+        exploded
+
+    }
+
+    /// Count the flags the user placed around the given cell
+    fn count_flags_around(&self, x_pos: usize, y_pos: usize) -> u8 {
+        let mut flags_around: u8 = 0;
+        for y in -1..=1 {
+            for x in -1..=1 {
+                if x == 0 && y == 0 { continue; }
+                let index_x = x_pos as isize + x;
+                let index_y = y_pos as isize + y;
+                if self.is_cell_valid(index_x, index_y)
+                    && self.cell(index_x as usize, index_y as usize).get_state() == CellMarker::HasBomb {
+                    flags_around += 1;
+                }
+            }
+        }
+        flags_around
+    }
+
+    /// Check if the given cell is already revealed
+    pub fn is_revealed(&self, x: usize, y: usize) -> bool {
+        self.is_cell_valid(x as isize, y as isize) && self.cell(x, y).is_revealed()
+    }
+
+    /// If the user marked (right mouse button) a cell, switch the marker,
+    /// a revealed cell can't be marked
+    fn mark_cell(&mut self, x: usize, y: usize) {
+        if !self.is_cell_valid(x as isize, y as isize) || self.cell(x, y).is_revealed() {
+            return;
+        }
+        match self.cell(x, y).get_state() {
+            CellMarker::GuessBomb => self.set_marker(x, y, CellMarker::None),
+            CellMarker::HasBomb => self.set_marker(x, y, CellMarker::GuessBomb),
+            CellMarker::None => self.set_marker(x, y, CellMarker::HasBomb),
+        }
+    }
+
+    /// Set or remove the given marker on a cell, the solver flags its mines without an outcome
+    pub(crate) fn toggle_marker(&mut self, x: usize, y: usize, marker: CellMarker) {
+        if !self.is_cell_valid(x as isize, y as isize) || self.cell(x, y).is_revealed() {
+            return;
+        }
+        if self.cell(x, y).get_state() == marker {
+            self.set_marker(x, y, CellMarker::None);
+        } else {
+            self.set_marker(x, y, marker);
+        }
+    }
+
+    /// Reveal a cell, a revealed number is chorded. This is the move of a left click
+    pub fn reveal(&mut self, x: usize, y: usize) -> Outcome {
+        self.begin_move();
+        if self.is_revealed(x, y) {
+            self.chord_cell(x, y);
+        } else {
+            self.open_cell(x, y);
+        }
+        self.get_outcome()
+    }
+
+    /// Chord a revealed number, all cells around without a flag are revealed
+    pub fn chord(&mut self, x: usize, y: usize) -> Outcome {
+        self.begin_move();
+        self.chord_cell(x, y);
+        self.get_outcome()
+    }
+
+    /// Set or remove a flag on a covered cell
+    pub fn flag(&mut self, x: usize, y: usize) -> Outcome {
+        self.begin_move();
+        self.toggle_marker(x, y, CellMarker::HasBomb);
+        self.get_outcome()
+    }
+
+    /// Set or remove a question mark on a covered cell
+    pub fn question(&mut self, x: usize, y: usize) -> Outcome {
+        self.begin_move();
+        self.toggle_marker(x, y, CellMarker::GuessBomb);
+        self.get_outcome()
+    }
+
+    /// Switch the marker of a covered cell from none to a flag to a question mark,
+    /// this is the move of a right click
+    pub fn mark(&mut self, x: usize, y: usize) -> Outcome {
+        self.begin_move();
+        self.mark_cell(x, y);
+        self.get_outcome()
+    }
 
+    /// The cells, that changed with the last move, and the state of the game
+    fn get_outcome(&self) -> Outcome {
+        let changed = self.delta.changes.iter()
+            .map(|change| Position::new((change.index % self.width) as u16, (change.index / self.width) as u16))
+            .collect();
+        Outcome { changed, state: self.get_state() }
     }
 
-    /// If the user marked (right mouse button) a cell, switch the marker
-    pub fn mark_cell(&mut self, x: usize, y: usize) {
-        match self.cell[x][y].get_state() {
-            CellMarker::GuessBomb => self.cell[x][y].set_state(CellMarker::None),
-            CellMarker::HasBomb => self.cell[x][y].set_state(CellMarker::GuessBomb),
-            CellMarker::None => self.cell[x][y].set_state(CellMarker::HasBomb),
+    /// Get the state of the game, a revealed bomb has lost it
+    pub fn get_state(&self) -> GameState {
+        if self.cells.iter().any(|cell| cell.is_revealed() && cell.is_bomb()) {
+            GameState::Lost
+        } else if self.check_win_condition() {
+            GameState::Won
+        } else if self.populated {
+            GameState::Running
+        } else {
+            GameState::Ready
         }
     }
 
-    /// Count the number of bombs that are marked correctly by the user
-    pub fn check_correct_flagged_bombs(&self) -> u16 {
+    /// Get the number of bombs minus the placed flags,
+    /// it is negative if the user placed more flags than bombs
+    pub fn get_bombs_left(&self) -> i64 {
+        let flags = self.cells.iter().filter(|cell| cell.get_state() == CellMarker::HasBomb).count();
+        self.max_bombs as i64 - flags as i64
+    }
+
+    /// Check if the bombs are placed, this happens with the first pick
+    pub fn is_populated(&self) -> bool {
+        self.populated
+    }
+
+    /// Count the revealed cells without bombs
+    pub fn count_revealed(&self) -> u64 {
+        self.cells.iter().filter(|cell| cell.is_revealed() && cell.is_empty()).count() as u64
+    }
+
+    /// Count the flags on a bomb and the flags on an empty cell
+    pub fn count_flags(&self) -> (u32, u32) {
         let mut correct_flag = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.cell[x][y].get_state() == CellMarker::HasBomb && self.cell[x][y].is_bomb() {
+        let mut wrong_flag = 0;
+        for cell in self.cells.iter() {
+            if cell.get_state() == CellMarker::HasBomb {
+                if cell.is_bomb() {
                     correct_flag += 1;
+                } else {
+                    wrong_flag += 1;
                 }
             }
         }
-        self.max_bombs - correct_flag
+        (correct_flag, wrong_flag)
+    }
+
+    /// Flag all bombs, that are not flagged yet, when the user won the game
+    pub fn flag_all_bombs(&mut self) {
+        for cell in self.cells.iter_mut() {
+            if cell.is_bomb() && !cell.is_revealed() {
+                cell.set_state(CellMarker::HasBomb);
+            }
+        }
     }
 
     /// Check here if the user won the game,
-    /// when all empty field are revealed and all bombs are correectly marked.
+    /// when all empty field are revealed. If all bombs must be flagged,
+    /// all bombs must be correctly marked too.
     pub fn check_win_condition(&self) -> bool {
         let mut correct_flag = 0;
         let mut empty_and_covered = 0;
         for y in 0..self.height {
             for x in 0..self.width {
-                if self.cell[x][y].get_state() == CellMarker::HasBomb && self.cell[x][y].is_bomb() {
+                if self.cell(x, y).get_state() == CellMarker::HasBomb && self.cell(x, y).is_bomb() {
                     correct_flag += 1;
                 }
-                if self.cell[x][y].is_empty()  && !self.cell[x][y].is_revealed() {
+                if self.cell(x, y).is_empty()  && !self.cell(x, y).is_revealed() {
                     empty_and_covered += 1;
                 }
             }
         }
-        if self.max_bombs == correct_flag && empty_and_covered == 0 {
+        if (!self.flag_all || self.max_bombs == correct_flag) && empty_and_covered == 0 {
             return true;
         }
         false
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_size_is_clamped() {
+        assert_eq!(BoardSize::Custom { width: 12, height: 8, mines: 20 }.get_dimension(), (12, 8, 20));
+        assert_eq!(BoardSize::Custom { width: 1, height: 100, mines: 0 }.get_dimension(), (MIN_BOARD_WIDTH, 100, 1));
+        // at least one cell stays free
+        assert_eq!(BoardSize::Custom { width: 3, height: 3, mines: 50 }.get_dimension(), (3, 3, 8));
+        // a wide board is cut to the maximum number of cells
+        let (w, h, _) = BoardSize::Custom { width: 60_000, height: 60_000, mines: 10 }.get_dimension();
+        assert_eq!((w, h), (60_000, MAX_BOARD_CELLS / 60_000));
+    }
+
+    #[test]
+    fn board_larger_than_the_old_array() {
+        let mut board = Board::new(BoardSize::Custom { width: 200, height: 120, mines: 2_000 });
+        board.populate_cells(0, 0);
+        assert_eq!((board.get_width(), board.get_height()), (200, 120));
+        assert_eq!(board.cells.len(), 24_000);
+        assert_eq!(board.cells.iter().filter(|cell| cell.is_bomb()).count(), 2_000);
+    }
+
+    #[test]
+    fn first_pick_is_safe() {
+        for _ in 0..50 {
+            let mut board = Board::new(BoardSize::Small);
+            assert!(!board.pick_cell(4, 4).is_exploded());
+            assert_eq!(board.cell(4, 4).get_bombs_around(), 0);
+            // without the safe neighbours only the picked cell is free
+            let mut board = Board::new(BoardSize::Custom { width: 3, height: 3, mines: 8 });
+            board.set_safe_neighbours(false);
+            assert!(!board.pick_cell(1, 1).is_exploded());
+            assert_eq!(board.cell(1, 1).get_bombs_around(), 8);
+        }
+    }
+
+    /// a small board, where every safe cell is revealed
+    fn create_cleared_board(flag_all: bool) -> Board {
+        let mut board = Board::new(BoardSize::Small);
+        board.set_flag_all(flag_all);
+        board.pick_cell(4, 4);
+        for index in 0..board.cells.len() {
+            if !board.cells[index].is_bomb() {
+                board.pick_cell(index % board.width, index / board.width);
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn revealing_all_safe_cells_wins() {
+        let board = create_cleared_board(false);
+        assert!(board.cells.iter().all(|cell| cell.get_state() == CellMarker::None));
+        assert!(board.check_win_condition());
+    }
+
+    #[test]
+    fn flag_all_needs_the_flags_too() {
+        let mut board = create_cleared_board(true);
+        assert!(!board.check_win_condition());
+        for index in 0..board.cells.len() {
+            if board.cells[index].is_bomb() {
+                board.toggle_marker(index % board.width, index / board.width, CellMarker::HasBomb);
+            }
+        }
+        assert!(board.check_win_condition());
+    }
+}
@@ -47,26 +47,31 @@ impl Cell {
         Cell::default()
     }
 
-    pub fn clear(&mut self) {
+    pub(crate) fn clear(&mut self) {
         self.content = CellContent::Empty;
         self.state = CellMarker::None;
         self.is_revealed = false;
         self.bombs_around = 0;
     }
 
-    pub fn set_content_bomb(&mut self) {
+    pub(crate) fn clear_content(&mut self) {
+        self.content = CellContent::Empty;
+        self.bombs_around = 0;
+    }
+
+    pub(crate) fn set_content_bomb(&mut self) {
         self.content = CellContent::Bomb;
     }
 
-    pub fn set_state(&mut self, mark: CellMarker) {
+    pub(crate) fn set_state(&mut self, mark: CellMarker) {
         self.state = mark;
     }
 
-    pub fn set_bombs_around(&mut self, bombs: u8) {
+    pub(crate) fn set_bombs_around(&mut self, bombs: u8) {
         self.bombs_around = bombs;
     }
 
-    pub fn set_revealed(&mut self, state: bool) {
+    pub(crate) fn set_revealed(&mut self, state: bool) {
         self.is_revealed = state;
     }
 
@@ -96,7 +101,48 @@ impl Cell {
         self.state
     }
 
-    pub fn get_gfx(&self) -> String {
+    /// Get a character for the save file: a dot is a covered cell, a star a covered bomb,
+    /// f and q are the markers on a cell and F and Q on a bomb, o and X are revealed
+    pub fn get_char(&self) -> char {
+        match (self.content, self.is_revealed, self.state) {
+            (CellContent::Empty, true, _) => 'o',
+            (CellContent::Bomb, true, _) => 'X',
+            (CellContent::Empty, false, CellMarker::None) => '.',
+            (CellContent::Empty, false, CellMarker::HasBomb) => 'f',
+            (CellContent::Empty, false, CellMarker::GuessBomb) => 'q',
+            (CellContent::Bomb, false, CellMarker::None) => '*',
+            (CellContent::Bomb, false, CellMarker::HasBomb) => 'F',
+            (CellContent::Bomb, false, CellMarker::GuessBomb) => 'Q',
+        }
+    }
+
+    /// Create a cell from a character of the save file, the bombs around must be counted again
+    pub fn from_char(c: char) -> Option<Cell> {
+        let (content, is_revealed, state) = match c {
+            'o' => (CellContent::Empty, true, CellMarker::None),
+            'X' => (CellContent::Bomb, true, CellMarker::None),
+            '.' => (CellContent::Empty, false, CellMarker::None),
+            'f' => (CellContent::Empty, false, CellMarker::HasBomb),
+            'q' => (CellContent::Empty, false, CellMarker::GuessBomb),
+            '*' => (CellContent::Bomb, false, CellMarker::None),
+            'F' => (CellContent::Bomb, false, CellMarker::HasBomb),
+            'Q' => (CellContent::Bomb, false, CellMarker::GuessBomb),
+            _ => return None,
+        };
+        Some(Cell { content, state, is_revealed, bombs_around: 0 })
+    }
+
+    /// Get the gfx of the cell with an other background color, used to highlight a cell.
+    /// The background is the parameter of the SGR sequence, like `42` or `48;5;28`
+    #[cfg(feature = "terminal")]
+    pub(crate) fn get_gfx_on(&self, background: &str) -> String {
+        let gfx = self.get_gfx();
+        let (color, symbol) = gfx.split_at(gfx.rfind('m').map_or(0, |i| i + 1));
+        format!("{}\x1b[{}m{}", color, background, symbol)
+    }
+
+    #[cfg(feature = "terminal")]
+    pub(crate) fn get_gfx(&self) -> String {
         let gfx: String;
         if self.is_revealed {
             match self.content {
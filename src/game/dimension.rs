@@ -4,6 +4,7 @@ pub struct Dimension {
     h: u16
 }
 
+#[allow(unused)]
 impl Dimension {
     pub fn new(width: u16, height: u16) -> Dimension {
         Dimension { w: width, h: height }
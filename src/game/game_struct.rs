@@ -1,81 +1,255 @@
-use crate::game::board::{Board,BoardSize};
+use crate::game::board::Board;
 use crate::game::dimension::Dimension;
+use crate::game::history::History;
 use crate::game::position::Position;
+use crate::game::replay::{Action, Move, Playback, Replay};
+use crate::game::savegame::SaveGame;
+use crate::game::scores::{HighScore, HighScores, MAX_NAME_LEN};
+use crate::game::screens::CustomBoardForm;
+use crate::game::settings::Settings;
+use crate::game::solver::{Hint, Reason, Solver};
+use crate::game::stats::{GameResult, Stats};
+use crate::game::viewport::Viewport;
 use crate::game::GAME_NAME;
 use crossterm::terminal;
+use std::time::Instant;
 
-/// an enum to the games condition
+/// the background colors of the analysis, from a safe cell to a certain mine
+const CHANCE_COLORS: [&str; 6] = ["48;5;28", "48;5;64", "48;5;100", "48;5;136", "48;5;130", "48;5;124"];
+
+/// the minimum size of the terminal to show the board, the other screens need more space
+const MIN_TERM_WIDTH: u16 = 50;
+const MIN_TERM_HEIGHT: u16 = 12;
+
+/// an enum to the games condition, every state is a screen on the screen stack
 #[derive(Copy,Clone,Debug,Default,PartialEq)]
 pub enum GameState {
+    CustomBoard,
     GiveUp,
+    HighScores,
     Loose,
+    Pause,
+    Replay,
     Rules,
     Running,
+    Settings,
     #[default]
     Start,
+    Statistics,
     Win,
 }
 
+impl GameState {
+
+    /// an overlay is drawn on top of the screen below it on the stack
+    pub fn is_overlay(&self) -> bool {
+        matches!(self, GameState::CustomBoard | GameState::Pause | GameState::Loose | GameState::Win)
+    }
+
+    /// the width and height of the terminal, that the screen needs to show all its lines
+    pub fn get_min_size(&self) -> (u16, u16) {
+        match self {
+            GameState::Start => (72, 14),
+            GameState::CustomBoard => (73, 22),
+            GameState::Settings => (67, 9),
+            GameState::HighScores => (73, 18),
+            GameState::Statistics => (80, 12),
+            GameState::Rules => (80, 10),
+            GameState::Replay => (69, MIN_TERM_HEIGHT),
+            GameState::Loose | GameState::Win => (62, MIN_TERM_HEIGHT),
+            GameState::Running | GameState::Pause | GameState::GiveUp => (MIN_TERM_WIDTH, MIN_TERM_HEIGHT),
+        }
+    }
+
+}
+
 /// the structure for the game screens often used vars
 #[derive(Clone,Debug)]
 pub struct Game {
     term_size: Dimension,
     title_bar: String,
     board: Board,
-    board_difficult: BoardSize,
     board_pos: Position,
-    board_size: Dimension,
+    viewport: Viewport,
+    cursor: Position,
+    move_count: u32,
     seconds_text: &'static str,
     pub seconds: u64,
+    pub timer_start: Instant,
     seconds_pos: Position,
     bombs_text: &'static str,
     bombs_pos: Position,
-    state: GameState,
-    pub pause: bool,
+    states: Vec<GameState>,
+    pub settings: Settings,
+    pub custom_form: CustomBoardForm,
+    pub high_scores: HighScores,
+    pub score_name: Option<String>,
+    pub score_page: usize,
+    pub rules_line: usize,
+    pub stats: Stats,
+    recorded: bool,
+    replay: Replay,
+    history: History,
+    hint: Option<Hint>,
+    hint_text: String,
+    hints: u32,
+    guess_warning: bool,
+    analysis: bool,
+    chances: Vec<f64>,
+    pub playback: Option<Playback>,
+    pub focus_pause: bool,
     pub update: bool,
     pub debug_mode: bool,
+    pub mouse_left: bool,
+    pub mouse_right: bool,
 }
 
 impl Game {
 
-    pub fn new(board_size: BoardSize) -> Game {
+    pub fn new(settings: Settings) -> Game {
         let (w,h) = terminal::size().unwrap_or_else(|err| {
             eprintln!("Error: {}", err);
             (0,0)
         });
-        let new_board = Board::new(board_size, false);
-        let bw = new_board.get_width();
-        let bh = new_board.get_height();
+        let mut game = Game {
+            term_size: Dimension::new(w, h),
+            title_bar: String::new(),
+            board: Board::new(settings.board_size),
+            board_pos: Position::default(),
+            viewport: Viewport::default(),
+            cursor: Position::default(),
+            move_count: 0,
+            seconds_text: "Time: ",
+            seconds: 0,
+            timer_start: Instant::now(),
+            seconds_pos: Position::default(),
+            bombs_text: "Bombs left: ",
+            bombs_pos: Position::default(),
+            states: vec![GameState::Start],
+            settings,
+            custom_form: CustomBoardForm::default(),
+            high_scores: HighScores::load(),
+            score_name: None,
+            score_page: 0,
+            rules_line: 0,
+            stats: Stats::load(),
+            recorded: false,
+            replay: Replay::default(),
+            history: History::default(),
+            hint: None,
+            hint_text: String::new(),
+            hints: 0,
+            guess_warning: false,
+            analysis: false,
+            chances: Vec::new(),
+            playback: None,
+            focus_pause: false,
+            update: true,
+            debug_mode: false,
+            mouse_left: false,
+            mouse_right: false,
+        };
+        // the board of the first game is created on the start screen, it gets the seed.
+        // A seed with the board size and the first picked cell starts the game at once
+        game.layout();
+        if game.settings.start.is_some() {
+            game.new_game();
+        }
+        game
+    }
+
+    /// create a new board with the current settings, the seed of the settings is used only once
+    fn new_board(&mut self) {
+        let mut new_board = self.settings.create_board();
+        if let Some(seed) = self.settings.seed.take() {
+            new_board.set_seed(seed);
+        }
+        // the keyboard cursor starts in the middle of the board
+        self.cursor = Position::new(new_board.get_width() / 2, new_board.get_height() / 2);
+        self.replay = Replay::new(self.settings, new_board.get_seed());
+        self.history = History::default();
+        self.hint = None;
+        self.hints = 0;
+        self.guess_warning = false;
+        // a game for the high scores is played without the chances
+        self.analysis &= self.settings.practice;
+        self.mouse_left = false;
+        self.mouse_right = false;
+        self.board = new_board;
+        self.recorded = false;
+        self.layout();
+    }
+
+    /// calculate the positions of all elements on the main screen
+    fn layout(&mut self) {
+        let (w,h) = self.term_size.get();
+        let bw = self.board.get_width();
+        let bh = self.board.get_height();
         let mut text = String::from("\x1b[97;100m");
         for _ in 0..w {
             text.push(' ');
         }
-        let x1 = (w as usize - GAME_NAME.len()) / 2;
-        let x2 = x1 + GAME_NAME.len();
+        let x1 = (w as usize).saturating_sub(GAME_NAME.len()) / 2;
         text.replace_range(x1.., GAME_NAME);
         text.push_str("\x1b[0m");
-        Game {
-            term_size: Dimension::new(w, h),
-            title_bar: text,
-            board: new_board,
-            board_difficult: board_size,
-            board_pos: Position::new((w - bw) / 2, (h - bh) / 2),
-            board_size: Dimension::new(bw, bh),
-            seconds_text: "Time: ",
-            seconds: 0,
-            seconds_pos: Position::new(w / 2 - 15, (h - bh) / 2 - 2),
-            bombs_text: "Bombs left: ",
-            bombs_pos: Position::new(w / 2 + 5, (h - bh) / 2 - 2),
-            state: GameState::Start,
-            pause: false,
-            update: false,
-            debug_mode: false
+        self.title_bar = text;
+        // leave space for the title, the counters, the frame, the scrollbars and the footer
+        self.viewport = Viewport::new(bw, bh, w.saturating_sub(4), h.saturating_sub(7));
+        self.viewport.follow(self.cursor.get_x(), self.cursor.get_y());
+        let vw = self.viewport.get_width();
+        let vh = self.viewport.get_height();
+        self.board_pos = Position::new((w - vw) / 2, (h - vh) / 2);
+        self.seconds_pos = Position::new((w / 2).saturating_sub(15), ((h - vh) / 2).saturating_sub(2));
+        self.bombs_pos = Position::new(w / 2 + 5, ((h - vh) / 2).saturating_sub(2));
+    }
+
+    /// start a new game with the current settings
+    pub fn new_game(&mut self) {
+        self.discard_saved_game();
+        self.new_board();
+        self.seconds = 0;
+        self.states = vec![GameState::Running];
+        // the first cell of the seed is picked for the player, the bombs are placed around it
+        if let Some((x, y)) = self.settings.start.take() {
+            self.set_cursor(x, y);
+            let exploded = self.pick_board_cell(x, y);
+            self.check_end_of_game(exploded);
         }
+        self.update = true;
     }
 
+    /// play a new board with the same size, it gets a new seed
     pub fn reset_board(&mut self) {
         self.board.clear();
-        self.board.populate_cells();
+        self.board.set_seed(rand::random());
+        self.replay = Replay::new(self.settings, self.board.get_seed());
+        self.history = History::default();
+        self.hint = None;
+        self.hints = 0;
+        self.guess_warning = false;
+        // a game for the high scores is played without the chances
+        self.analysis &= self.settings.practice;
+        self.mouse_left = false;
+        self.mouse_right = false;
+        self.recorded = false;
+        self.seconds = 0;
+        self.states = vec![GameState::Running];
+        self.update = true;
+    }
+
+    /// go back to the start menu
+    pub fn show_menu(&mut self) {
+        self.states = vec![GameState::Start];
+        self.update = true;
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.board.get_seed()
+    }
+
+    /// the seed with the size, the first picked cell and the rules, it creates the same board again
+    pub fn get_seed_text(&self) -> String {
+        self.settings.get_seed_text(self.board.get_seed(), self.board.get_start())
     }
 
     pub fn get_title(&self) -> &str {
@@ -87,24 +261,11 @@ impl Game {
     }
 
     pub fn get_bombs_text(&self) -> String {
-        format!("{}\x1b[32m{:2}\x1b[0m", self.bombs_text, self.get_flagged_bombs())
+        format!("{}\x1b[32m{:2}\x1b[0m", self.bombs_text, self.get_bombs_left())
     }
 
     pub fn get_formated_seconds(&self) -> String {
-        let mut secs = self.seconds;
-        if secs < 60 {
-            format!("0:{:02}", secs)
-        } else if secs < 3600 {
-            let m = secs / 60;
-            secs -= m * 60;
-            format!("{}:{:02}", m, secs)
-        } else {
-            let h = secs / 3600;
-            secs -= h * 3600;
-            let m = secs / 60;
-            secs -= m * 60;
-            format!("{}:{:02}:{:02}", h, m, secs)
-        }
+        format_seconds(self.seconds)
     }
 
     pub fn get_term_width(&self) -> u16 {
@@ -115,12 +276,62 @@ impl Game {
         self.term_size.get_height()
     }
 
+    /// the terminal was resized, calculate the positions on the screen again
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.term_size.set(width, height);
+        self.layout();
+        self.update = true;
+    }
+
+    /// the size of the terminal, that the shown screens need, an overlay needs the space of the screen below too
+    pub fn get_min_term_size(&self) -> (u16, u16) {
+        let first = self.states.iter().rposition(|state| !state.is_overlay()).unwrap_or(0);
+        self.states[first..].iter().map(|state| state.get_min_size())
+            .fold((0, 0), |(w, h), (state_w, state_h)| (w.max(state_w), h.max(state_h)))
+    }
+
+    /// check if the terminal is too small to show the screens
+    pub fn is_term_too_small(&self) -> bool {
+        let (w, h) = self.get_min_term_size();
+        self.get_term_width() < w || self.get_term_height() < h
+    }
+
+    /// replace the screen on top of the stack
     pub fn set_gamestate(&mut self, game_state: GameState) {
-        self.state = game_state;
+        // an unfinished game is saved, if that fails it is given up
+        if game_state == GameState::GiveUp && self.save_game().is_err() {
+            self.record_game(GameResult::GaveUp);
+        }
+        self.states.pop();
+        self.states.push(game_state);
+        self.update = true;
     }
 
+    /// get the state of the screen on top of the stack
     pub fn get_gamestate(&self) -> GameState {
-        self.state
+        self.states.last().copied().unwrap_or(GameState::GiveUp)
+    }
+
+    /// get the whole screen stack, the last state is on top
+    pub fn get_gamestates(&self) -> &[GameState] {
+        &self.states
+    }
+
+    /// show a screen on top of the current screen
+    pub fn push_gamestate(&mut self, game_state: GameState) {
+        self.states.push(game_state);
+        self.update = true;
+    }
+
+    /// close the screen on top and go back to the screen below
+    pub fn pop_gamestate(&mut self) {
+        self.states.pop();
+        self.update = true;
+    }
+
+    /// the timer runs only, if the board is on top of the screen stack and can be seen
+    pub fn is_timer_running(&self) -> bool {
+        self.get_gamestate() == GameState::Running && !self.is_term_too_small()
     }
 
     pub fn get_board_x(&self) -> u16 {
@@ -131,19 +342,141 @@ impl Game {
         self.board_pos.get_y()
     }
 
-    pub fn get_board_width(&self) -> u16 {
-        self.board_size.get_width()
+    pub fn get_viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    pub fn get_view_width(&self) -> u16 {
+        self.viewport.get_width()
+    }
+
+    pub fn get_view_height(&self) -> u16 {
+        self.viewport.get_height()
+    }
+
+    pub fn scroll_view(&mut self, dx: i32, dy: i32) {
+        if self.viewport.scroll(dx, dy) {
+            self.update = true;
+        }
+    }
+
+    pub fn get_cursor(&self) -> (usize,usize) {
+        (self.cursor.get_x() as usize, self.cursor.get_y() as usize)
+    }
+
+    /// set the keyboard cursor to a cell of the board, the viewport follows the cursor
+    pub fn set_cursor(&mut self, x: usize, y: usize) {
+        let x = x.min(self.board.get_width() as usize - 1) as u16;
+        let y = y.min(self.board.get_height() as usize - 1) as u16;
+        self.cursor.set(x, y);
+        self.viewport.follow(x, y);
+        self.update = true;
     }
 
-    pub fn get_board_height(&self) -> u16 {
-        self.board_size.get_height()
+    /// move the keyboard cursor, it stops at the edges of the board
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let x = (self.cursor.get_x() as i32 + dx).max(0) as usize;
+        let y = (self.cursor.get_y() as i32 + dy).max(0) as usize;
+        self.set_cursor(x, y);
     }
 
-    pub fn get_board_gfx(&mut self) -> Vec<String> {
+    /// add a digit to the count, that repeats the next cursor move
+    pub fn push_move_count(&mut self, digit: u32) {
+        self.move_count = (self.move_count * 10 + digit).min(u16::MAX as u32);
+    }
+
+    /// get the count for the cursor move and reset it, without a count the cursor moves once
+    pub fn take_move_count(&mut self) -> i32 {
+        let count = self.move_count.max(1);
+        self.move_count = 0;
+        count as i32
+    }
+
+    /// convert a terminal position into a cell of the board, if it is inside the viewport
+    pub fn get_board_cell(&self, screen_x: u16, screen_y: u16) -> Option<(usize,usize)> {
+        let x = screen_x as i32 - self.board_pos.get_x() as i32;
+        let y = screen_y as i32 - self.board_pos.get_y() as i32;
+        if !self.viewport.contains(x, y) {
+            return None;
+        }
+        Some(((x + self.viewport.get_x() as i32) as usize, (y + self.viewport.get_y() as i32) as usize))
+    }
+
+    pub fn get_board_gfx(&self) -> Vec<String> {
+        let x = self.viewport.get_x() as usize;
+        let y = self.viewport.get_y() as usize;
+        let w = self.viewport.get_width() as usize;
+        let h = self.viewport.get_height() as usize;
+        let cursor = Some(self.get_cursor());
         if self.debug_mode {
-            self.board.dbg_gfx()
+            self.board.dbg_gfx(x, y, w, h, cursor)
+        } else {
+            self.board.get_gfx(x, y, w, h, cursor, |x, y| self.get_cell_background(x, y))
+        }
+    }
+
+    /// the hint shows a safe cell green, a mine red and a guess yellow,
+    /// the analysis tints the cells by their chance to be a mine
+    fn get_cell_background(&self, x: usize, y: usize) -> Option<String> {
+        if let Some(hint) = self.hint && (hint.x, hint.y) == (x, y) {
+            let background = match (hint.is_mine, hint.reason) {
+                (true, _) => "41",
+                (false, Some(_)) => "42",
+                (false, None) => "43",
+            };
+            return Some(String::from(background));
+        }
+        let chance = self.get_chance(x, y)?;
+        let color = match chance {
+            0.0 => 0,
+            1.0 => CHANCE_COLORS.len() - 1,
+            _ => 1 + ((chance * 4.0) as usize).min(3),
+        };
+        Some(String::from(CHANCE_COLORS[color]))
+    }
+
+    /// show or hide the chances of the covered cells to be a mine
+    pub fn toggle_analysis(&mut self) {
+        // the chances would solve a game for the high scores
+        if self.get_gamestate() == GameState::Running && !self.settings.practice {
+            return;
+        }
+        self.analysis = !self.analysis;
+        self.update_analysis();
+        self.update = true;
+    }
+
+    /// calculate the chances of the analysis again, the board changed
+    pub fn update_analysis(&mut self) {
+        if self.analysis {
+            (self.chances, _) = Solver::from_board(&self.board).calculate_chances();
         } else {
-            self.board.get_gfx()
+            self.chances.clear();
+        }
+    }
+
+    /// get the chance of a cell to be a mine, if the analysis is shown.
+    /// Only the numbers are known, so a revealed mine after a loss has a chance too
+    pub fn get_chance(&self, x: usize, y: usize) -> Option<f64> {
+        if !self.analysis || self.debug_mode {
+            return None;
+        }
+        let cell = self.board.get_cell(x, y);
+        if cell.is_revealed() && cell.is_empty() {
+            return None;
+        }
+        self.chances.get(y * self.board.get_width() as usize + x).copied()
+    }
+
+    /// the chance of the cell under the cursor as a text, if the analysis is shown
+    pub fn get_chance_text(&self) -> Option<String> {
+        let (x, y) = self.get_cursor();
+        if !self.analysis {
+            return None;
+        }
+        match self.get_chance(x, y) {
+            Some(chance) => Some(format!("Mine chance at {},{}: {:.1}%", x + 1, y + 1, chance * 100.0)),
+            None => Some(format!("The cell at {},{} is open.", x + 1, y + 1)),
         }
     }
 
@@ -163,19 +496,331 @@ impl Game {
         self.bombs_pos.get_y()
     }
 
-    pub fn get_flagged_bombs(&self) -> u16 {
-        self.board.check_correct_flagged_bombs()
+    pub fn get_bombs_left(&self) -> i64 {
+        self.board.get_bombs_left()
+    }
+
+    pub fn get_flag_count(&self) -> (u32, u32) {
+        self.board.count_flags()
+    }
+
+    /// the game time in milliseconds, the current second is counted by the timer
+    pub fn get_elapsed_ms(&self) -> u64 {
+        self.seconds * 1_000 + (self.timer_start.elapsed().as_millis() as u64).min(999)
+    }
+
+    /// play a move on the board and record it for the replay, returns TRUE if a bomb exploded
+    fn play_move(&mut self, action: Action, x: usize, y: usize) -> bool {
+        let mv = Move { ms: self.get_elapsed_ms(), action, x, y };
+        self.replay.moves.push(mv);
+        self.hint = None;
+        let populated = self.board.is_populated();
+        let exploded = mv.apply(&mut self.board, &mut self.history);
+        // on a large board the time to find a board without guesses can run out
+        if !populated && self.board.is_populated() && self.settings.no_guess && !self.board.is_guess_free() {
+            self.guess_warning = true;
+        }
+        exploded
+    }
+
+    /// show a hint on the board, the hint is explained below the board
+    pub fn request_hint(&mut self) {
+        if self.get_gamestate() != GameState::Running {
+            return;
+        }
+        // the shown hint is not counted again
+        if self.hint.is_some() {
+            return;
+        }
+        // before the first move every cell is safe, that is no help
+        if !self.board.is_populated() {
+            let (x, y) = self.get_cursor();
+            self.hint = Some(Hint { x, y, is_mine: false, reason: None, source: (x, y), chance: 0.0 });
+            self.hint_text = String::from("The first move is always safe, just start here.");
+        } else {
+            let Some(hint) = Solver::find_hint(&self.board) else { return; };
+            self.hint_text = Game::explain_hint(&hint);
+            self.hint = Some(hint);
+            self.hints += 1;
+        }
+        if let Some(hint) = self.hint {
+            self.set_cursor(hint.x, hint.y);
+        }
+        self.update = true;
+    }
+
+    /// a text, why the cell of the hint is safe or a mine
+    fn explain_hint(hint: &Hint) -> String {
+        let cell = format!("{},{}", hint.x + 1, hint.y + 1);
+        let source = format!("{},{}", hint.source.0 + 1, hint.source.1 + 1);
+        let what = if hint.is_mine { "a mine" } else { "safe" };
+        match hint.reason {
+            Some(Reason::Satisfied) => format!("The number at {} has all its mines, {} is {}.", source, cell, what),
+            Some(Reason::AllMines) => format!("The number at {} has only mines around, {} is {}.", source, cell, what),
+            Some(Reason::Pattern) => format!("A pattern like 1-2 with the number at {}, {} is {}.", source, cell, what),
+            Some(Reason::MineCount) => format!("The mine counter tells it, {} is {}.", cell, what),
+            Some(Reason::AllLayouts) => format!("Every layout of the mines, that fits the numbers, leaves {} {}.", cell, what),
+            None => format!("No safe cell is known, {} is a guess with a {:.0}% chance of a mine.", cell, hint.chance * 100.0),
+        }
+    }
+
+    /// get the hint and its text, if the player asked for one
+    pub fn get_hint(&self) -> Option<(&Hint, &str)> {
+        self.hint.as_ref().map(|hint| (hint, self.hint_text.as_str()))
+    }
+
+    /// a warning, if the board of the no guess mode may need a guess
+    pub fn get_guess_warning(&self) -> Option<&str> {
+        self.guess_warning.then_some("No board without guesses found, you may need one.")
+    }
+
+    /// the number of hints in this game
+    pub fn get_hints(&self) -> u32 {
+        self.hints
+    }
+
+    /// undo the last move. A lost game can be continued in the practice mode only
+    pub fn undo_move(&mut self) {
+        match self.get_gamestate() {
+            GameState::Running if self.history.can_undo() => {}
+            GameState::Loose if self.settings.practice && self.history.is_last_exploded() => {
+                self.pop_gamestate();
+                self.recorded = false;
+            }
+            _ => return,
+        }
+        self.play_move(Action::Undo, 0, 0);
+        self.update = true;
+    }
+
+    /// do the last undone move again, returns TRUE if a bomb exploded again
+    pub fn redo_move(&mut self) -> bool {
+        if self.get_gamestate() != GameState::Running || !self.history.can_redo() {
+            return false;
+        }
+        self.update = true;
+        self.play_move(Action::Redo, 0, 0)
+    }
+
+    /// check if the last move can be undone on the end screen
+    pub fn can_undo_loss(&self) -> bool {
+        self.get_gamestate() == GameState::Loose && self.settings.practice && self.history.is_last_exploded()
+    }
+
+    /// pick a cell, a click on a revealed number chords it
+    pub fn pick_board_cell(&mut self, x: usize, y: usize) -> bool {
+        self.play_move(Action::Reveal, x, y)
+    }
+
+    pub fn chord_board_cell(&mut self, x: usize, y: usize) -> bool {
+        self.play_move(Action::Chord, x, y)
     }
 
-    pub fn pick_board_cell(&mut self, x: i16, y: i16) -> bool {
-        self.board.pick_cell(x as usize, y as usize)
+    pub fn mark_board_cell(&mut self, x: usize, y: usize) {
+        self.play_move(Action::Mark, x, y);
     }
 
-    pub fn mark_board_cell(&mut self, x: i16, y: i16) {
-        self.board.mark_cell(x as usize, y as usize);
+    pub fn flag_board_cell(&mut self, x: usize, y: usize) {
+        self.play_move(Action::Flag, x, y);
+    }
+
+    pub fn question_board_cell(&mut self, x: usize, y: usize) {
+        self.play_move(Action::Question, x, y);
     }
 
     pub fn check_win_condition(&self) -> bool {
         self.board.check_win_condition()
     }
+
+    /// check the end of the game after a move, the player loses if a bomb was uncovered
+    pub fn check_end_of_game(&mut self, exploded: bool) {
+        if self.get_gamestate() != GameState::Running {
+            return;
+        }
+        if exploded {
+            self.record_game(GameResult::Lost);
+            self.push_gamestate(GameState::Loose);
+        } else if self.check_win_condition() {
+            self.board.flag_all_bombs();
+            self.record_game(GameResult::Won);
+            self.push_gamestate(GameState::Win);
+            // ask for the name, if the time is good enough for the high scores
+            let (width, height, mines) = self.get_board_size();
+            if !self.settings.practice && self.high_scores.get_rank(width, height, mines, self.seconds).is_some() {
+                self.score_name = Some(std::env::var("USER").unwrap_or_default().chars().take(MAX_NAME_LEN).collect());
+            }
+        }
+        self.update = true;
+    }
+
+    /// the width, the height and the number of mines of the board
+    pub fn get_board_size(&self) -> (u16, u16, u32) {
+        (self.board.get_width(), self.board.get_height(), self.board.get_max_bombs())
+    }
+
+    /// count the game in the statistics, once and only if the player made a move
+    fn record_game(&mut self, result: GameResult) {
+        if self.recorded || !self.board.is_populated() {
+            return;
+        }
+        self.recorded = true;
+        // a game in the practice mode is not counted
+        if !self.settings.practice {
+            let (width, height, mines) = self.get_board_size();
+            self.stats.record(width, height, mines, result, self.seconds, self.board.count_revealed());
+            // the terminal is in raw mode, the statistics of this session are kept anyway
+            let _ = self.stats.save();
+        }
+        // the board of the replay is created with the bombs, the seed is the one of the used board
+        self.replay.seed = self.board.get_seed();
+        self.replay.bombs = self.board.get_bombs();
+        let _ = self.replay.save();
+        SaveGame::remove();
+    }
+
+    /// check if the player made a move and the game is not over yet
+    pub fn is_game_in_progress(&self) -> bool {
+        self.board.is_populated() && !self.recorded
+    }
+
+    /// save the game, if it is in progress
+    pub fn save_game(&self) -> std::io::Result<()> {
+        if !self.is_game_in_progress() {
+            return Ok(());
+        }
+        let saved = SaveGame {
+            settings: self.settings,
+            board: self.board.clone(),
+            seconds: self.seconds,
+            cursor: self.cursor.get(),
+            moves: self.replay.moves.clone(),
+            hints: self.hints,
+        };
+        saved.save()
+    }
+
+    /// continue the saved game, it starts paused. The file is kept until the game is over,
+    /// so the game can be continued after a crash too
+    pub fn resume_game(&mut self) {
+        let Some(saved) = SaveGame::load() else {
+            // a broken file can't be continued
+            SaveGame::remove();
+            self.update = true;
+            return;
+        };
+        self.settings = saved.settings;
+        self.replay = Replay { settings: saved.settings, seed: saved.board.get_seed(), bombs: Vec::new(), moves: saved.moves };
+        self.history = History::default();
+        self.hint = None;
+        self.hints = saved.hints;
+        // a game for the high scores is played without the chances
+        self.analysis &= self.settings.practice;
+        self.board = saved.board;
+        self.seconds = saved.seconds;
+        self.cursor = Position::new(saved.cursor.0, saved.cursor.1);
+        self.recorded = false;
+        self.layout();
+        self.states = vec![GameState::Running, GameState::Pause];
+        self.update = true;
+    }
+
+    /// watch the replay of the last game, nothing is recorded during the playback
+    pub fn start_playback(&mut self) {
+        let Some(replay) = Replay::load() else { return; };
+        self.settings.board_size = replay.settings.board_size;
+        self.playback = Some(Playback::new(replay));
+        self.seek_playback(0);
+        self.layout();
+        self.states = vec![GameState::Replay];
+    }
+
+    /// play the moves of the replay, whose time has come
+    pub fn update_playback(&mut self) {
+        let Some(playback) = &mut self.playback else { return; };
+        let seconds = playback.get_ms() / 1_000;
+        while let Some(mv) = playback.next_move() {
+            mv.apply(&mut self.board, &mut playback.history);
+            if mv.has_cell() {
+                self.cursor.set(mv.x as u16, mv.y as u16);
+                self.viewport.follow(mv.x as u16, mv.y as u16);
+            }
+            self.update = true;
+        }
+        // the playback stops with the last move
+        if playback.is_finished() && !playback.paused {
+            playback.paused = true;
+            self.update = true;
+        }
+        if playback.is_finished() && self.board.check_win_condition() {
+            self.board.flag_all_bombs();
+        }
+        if playback.get_ms() / 1_000 != seconds {
+            self.update = true;
+        }
+        self.seconds = playback.get_ms() / 1_000;
+    }
+
+    /// go to the given move of the replay, the board is played again from the start
+    pub fn seek_playback(&mut self, next: usize) {
+        let Some(playback) = &mut self.playback else { return; };
+        self.board = playback.replay.create_board();
+        self.cursor = Position::new(self.board.get_width() / 2, self.board.get_height() / 2);
+        playback.set_next(next);
+        playback.history = History::default();
+        for mv in &playback.replay.moves[..playback.next] {
+            mv.apply(&mut self.board, &mut playback.history);
+            if mv.has_cell() {
+                self.cursor.set(mv.x as u16, mv.y as u16);
+            }
+        }
+        self.seconds = playback.get_ms() / 1_000;
+        self.viewport.follow(self.cursor.get_x(), self.cursor.get_y());
+        // the playback board is never counted or saved
+        self.recorded = true;
+        self.update = true;
+    }
+
+    /// a new game replaces the saved game, the saved game counts as given up
+    fn discard_saved_game(&mut self) {
+        if !SaveGame::exists() {
+            return;
+        }
+        if let Some(saved) = SaveGame::load() && !saved.settings.practice {
+            let board = &saved.board;
+            self.stats.record(board.get_width(), board.get_height(), board.get_max_bombs(),
+                GameResult::GaveUp, saved.seconds, board.count_revealed());
+            let _ = self.stats.save();
+        }
+        SaveGame::remove();
+    }
+
+    /// add the won game with the entered name to the high scores
+    pub fn save_high_score(&mut self) {
+        let Some(name) = self.score_name.take() else { return; };
+        let (width, height, mines) = self.get_board_size();
+        let score = HighScore { width, height, mines, seconds: self.seconds, seed: self.get_seed(), hints: self.hints, name };
+        if self.high_scores.add(score).is_some() {
+            // the terminal is in raw mode, the score stays in the table of this session anyway
+            let _ = self.high_scores.save();
+        }
+        self.update = true;
+    }
+}
+
+/// format the seconds as a time like 1:02:03
+pub fn format_seconds(seconds: u64) -> String {
+    let mut secs = seconds;
+    if secs < 60 {
+        format!("0:{:02}", secs)
+    } else if secs < 3600 {
+        let m = secs / 60;
+        secs -= m * 60;
+        format!("{}:{:02}", m, secs)
+    } else {
+        let h = secs / 3600;
+        secs -= h * 3600;
+        let m = secs / 60;
+        secs -= m * 60;
+        format!("{}:{:02}:{:02}", h, m, secs)
+    }
 }
@@ -0,0 +1,136 @@
+// part of the Minesweeper game for the terminal
+#[cfg(feature = "terminal")]
+use crate::game::board::Board;
+use crate::game::cell::Cell;
+
+/// A cell before and after a move
+#[derive(Copy,Clone,Debug)]
+pub struct CellChange {
+    pub index: usize,
+    pub before: Cell,
+    pub after: Cell,
+}
+
+/// All changes of one move, with them the move can be undone and done again.
+/// The bombs of the first pick are not part of it, an undo keeps the layout of the board
+#[derive(Clone,Debug,Default)]
+pub struct Delta {
+    pub changes: Vec<CellChange>,
+    pub exploded: bool,
+}
+
+impl Delta {
+
+    /// a move without any changes, like a flag on a revealed cell
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+}
+
+/// The moves, that can be undone, and the undone moves, that can be done again
+#[cfg(feature = "terminal")]
+#[derive(Clone,Debug,Default)]
+pub struct History {
+    undo: Vec<Delta>,
+    redo: Vec<Delta>,
+}
+
+#[cfg(feature = "terminal")]
+impl History {
+
+    /// add the changes of a new move, the undone moves are lost
+    pub fn push(&mut self, delta: Delta) {
+        if delta.is_empty() {
+            return;
+        }
+        self.undo.push(delta);
+        self.redo.clear();
+    }
+
+    /// check if there is a move to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// check if there is a move to do again
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// check if the last move uncovered a bomb
+    pub fn is_last_exploded(&self) -> bool {
+        self.undo.last().is_some_and(|delta| delta.exploded)
+    }
+
+    /// undo the last move, returns FALSE if there is none
+    pub fn undo(&mut self, board: &mut Board) -> bool {
+        let Some(delta) = self.undo.pop() else { return false; };
+        board.undo(&delta);
+        self.redo.push(delta);
+        true
+    }
+
+    /// do the last undone move again, returns TRUE if a bomb exploded again
+    pub fn redo(&mut self, board: &mut Board) -> bool {
+        let Some(delta) = self.redo.pop() else { return false; };
+        board.redo(&delta);
+        let exploded = delta.exploded;
+        self.undo.push(delta);
+        exploded
+    }
+
+}
+
+#[cfg(all(test, feature = "terminal"))]
+mod tests {
+    use super::*;
+    use crate::game::board::{BoardSize, GameState};
+
+    /// play a reveal and keep its changes in the history
+    fn reveal(board: &mut Board, history: &mut History, x: usize, y: usize) {
+        board.reveal(x, y);
+        history.push(board.take_delta());
+    }
+
+    #[test]
+    fn undo_and_redo_a_move() {
+        let mut board = Board::new(BoardSize::Small);
+        let mut history = History::default();
+        board.set_seed(7);
+        reveal(&mut board, &mut history, 4, 4);
+        let opened = board.count_revealed();
+        let bombs = board.get_bombs_left();
+        let index = (0..81).find(|index| !board.get_cell(index % 9, index / 9).is_revealed()).unwrap();
+        board.flag(index % 9, index / 9);
+        history.push(board.take_delta());
+        assert!(history.undo(&mut board));
+        assert_eq!(board.get_bombs_left(), bombs);
+        assert!(history.can_redo());
+        assert!(!history.redo(&mut board));
+        assert_eq!(board.get_bombs_left(), bombs - 1);
+        assert_eq!(board.count_revealed(), opened);
+        // a new move drops the undone moves
+        history.undo(&mut board);
+        board.question(index % 9, index / 9);
+        history.push(board.take_delta());
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn undo_of_the_first_move_keeps_the_bombs() {
+        let mut board = Board::new(BoardSize::Small);
+        let mut history = History::default();
+        board.set_seed(7);
+        reveal(&mut board, &mut history, 4, 4);
+        let bombs = board.get_bombs();
+        assert!(history.undo(&mut board));
+        assert!(!history.can_undo());
+        assert_eq!(board.count_revealed(), 0);
+        assert_eq!(board.get_bombs(), bombs);
+        assert_eq!(board.get_state(), GameState::Running);
+        history.redo(&mut board);
+        assert_eq!(board.get_bombs(), bombs);
+        assert!(board.get_cell(4, 4).is_revealed());
+    }
+}
@@ -1,10 +1,9 @@
 use crate::game::game_struct::{Game,GameState};
+use crate::game::screens;
 use crossterm::event;
 
-/// this function catches all user events and process or returns em
-pub fn user_input(game: &mut Game) -> GameState {
-
-    let mut current_state = game.get_gamestate();
+/// this function catches all user events and passes them to the screen on top
+pub fn user_input(game: &mut Game) {
 
     // this function did not block the whole terminal for user input
     if event::poll(std::time::Duration::from_millis(50)).unwrap() {
@@ -13,93 +12,48 @@ pub fn user_input(game: &mut Game) -> GameState {
 
             // process keyboard events
             event::Event::Key(event) => {
-                match event.code {
-
-                    // show the rules screen and stop the timer
-                    event::KeyCode::F(1) => {
-                        game.set_gamestate(GameState::Rules);
-                        game.pause = true;
-                    },
-
-                    // exit the loop, to quit the game
-                    event::KeyCode::F(10) | event::KeyCode::Char('q') => {
-                        if game.get_gamestate() != GameState::Rules {
-                            game.set_gamestate(GameState::GiveUp);
-                        }
-                    },
-
-                    // toggle the debug mode
-                    event::KeyCode::F(12) => {
-                        // and do it only if the app compiled in debug mode
-                        if cfg!(debug_assertions) {
-                            game.debug_mode = !game.debug_mode;
-                            game.update = true;
-                        }
-                    },
-
-                    event::KeyCode::Char('e') => {
-                        game.set_gamestate(GameState::Running);
-                        game.pause = false;
-                        game.update = true;
-                    },
-
-                    event::KeyCode::Char('p') => {
-                        game.pause = !game.pause;
-                        if !game.pause {
-                            game.update = true;
-                        }
-                    },
-
-                    event::KeyCode::Char('s') => {
-                        if game.get_gamestate() == GameState::Loose || game.get_gamestate() == GameState::Win {
-                            game.set_gamestate(GameState::Start);
-                        }
-                    }
 
-                    _ => {}
+                if event.kind != event::KeyEventKind::Press {
+                    return;
                 }
 
                 // in raw terminal mode, the CTRL + C ist deactivated, so manually activate it
                 if event.modifiers == event::KeyModifiers::CONTROL && event.code == event::KeyCode::Char('c') {
-                    std::process::exit(99);
+                    game.set_gamestate(GameState::GiveUp);
+                    return;
                 }
 
-            },
-
-            // process mouse events
-            event::Event::Mouse(mouse_event) => {
-
-                let mouse_x = mouse_event.column;
-                let mouse_y = mouse_event.row;
-                let cell_x = mouse_x as i16 - game.get_board_x() as i16;
-                let cell_y = mouse_y as i16 - game.get_board_y() as i16;
-
-                // process left click
-                if mouse_event.kind == event::MouseEventKind::Down(event::MouseButton::Left) {
-                    // if pick_cell() is true, the player clicked a field with a bomb
-                    if game.pick_board_cell(cell_x, cell_y) {
-                        game.set_gamestate(GameState::Loose);
-                        game.pause = true;
+                // toggle the debug mode
+                if event.code == event::KeyCode::F(12) {
+                    // and do it only if the app compiled in debug mode
+                    if cfg!(debug_assertions) {
+                        game.debug_mode = !game.debug_mode;
+                        game.update = true;
                     }
-                    game.update = true;
-
-                // process right click
-                } else if mouse_event.kind == event::MouseEventKind::Down(event::MouseButton::Right) {
-                    game.mark_board_cell(cell_x, cell_y);
-                    game.update = true;
+                    return;
                 }
 
-
+                screens::handle_key(game, &event);
 
             },
 
-            // check the focus here, to stop the timer
-            event::Event::FocusLost => {
-                game.pause = true;
+            // process mouse events
+            event::Event::Mouse(mouse_event) => screens::handle_mouse(game, &mouse_event),
+
+            // the screens are laid out again for the new size
+            event::Event::Resize(width, height) => game.resize(width, height),
+
+            // check the focus here, to stop the timer, the game is saved in case it is not continued
+            event::Event::FocusLost if game.get_gamestate() == GameState::Running => {
+                let _ = game.save_game();
+                game.focus_pause = true;
+                game.push_gamestate(GameState::Pause);
             }
 
-            event::Event::FocusGained => {
-                game.pause = false;
+            // continue only, if the game was paused by the lost focus
+            event::Event::FocusGained if game.focus_pause && game.get_gamestate() == GameState::Pause => {
+                game.focus_pause = false;
+                game.pop_gamestate();
             }
 
             _ => {}
@@ -108,5 +62,4 @@ pub fn user_input(game: &mut Game) -> GameState {
 
     }
 
-    current_state
 }
@@ -4,6 +4,7 @@ pub struct Position {
     y: u16
 }
 
+#[allow(unused)]
 impl Position {
     pub fn new(x_pos: u16, y_pos: u16) -> Position {
         Position { x: x_pos, y: y_pos }
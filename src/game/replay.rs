@@ -0,0 +1,350 @@
+// part of the Minesweeper game for the terminal
+use crate::game::board::Board;
+use crate::game::history::History;
+use crate::game::settings::Settings;
+use crate::game::storage::{self, Values};
+use std::time::Instant;
+
+/// the file of the replay of the last game in the data directory
+const REPLAY_FILE: &str = "replay.txt";
+
+/// the first line of the file, the version changes with every new format
+const HEADER: &str = "minesweeper replay";
+const VERSION: u32 = 2;
+
+/// the speeds of the playback, the normal speed is in the middle
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const NORMAL_SPEED: usize = 2;
+
+/// the moves of the player
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub enum Action {
+    Reveal,
+    Chord,
+    Mark,
+    Flag,
+    Question,
+    Undo,
+    Redo,
+}
+
+impl Action {
+
+    /// the name of the action in the file
+    fn get_name(&self) -> &'static str {
+        match self {
+            Action::Reveal => "reveal",
+            Action::Chord => "chord",
+            Action::Mark => "mark",
+            Action::Flag => "flag",
+            Action::Question => "question",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+        }
+    }
+
+    /// get the action of a name in the file
+    fn from_name(name: &str) -> Option<Action> {
+        match name {
+            "reveal" => Some(Action::Reveal),
+            "chord" => Some(Action::Chord),
+            "mark" => Some(Action::Mark),
+            "flag" => Some(Action::Flag),
+            "question" => Some(Action::Question),
+            "undo" => Some(Action::Undo),
+            "redo" => Some(Action::Redo),
+            _ => None,
+        }
+    }
+
+}
+
+/// a move on the board, the time is the game time in milliseconds
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub struct Move {
+    pub ms: u64,
+    pub action: Action,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Move {
+
+    /// play the move on the board, returns TRUE if a bomb exploded.
+    /// A reveal on a revealed number chords it, like a click does.
+    /// The changes are added to the history, undo and redo use it
+    pub fn apply(&self, board: &mut Board, history: &mut History) -> bool {
+        let outcome = match self.action {
+            Action::Undo => {
+                history.undo(board);
+                return false;
+            }
+            Action::Redo => return history.redo(board),
+            Action::Reveal => board.reveal(self.x, self.y),
+            Action::Chord => board.chord(self.x, self.y),
+            Action::Mark => board.mark(self.x, self.y),
+            Action::Flag => board.flag(self.x, self.y),
+            Action::Question => board.question(self.x, self.y),
+        };
+        history.push(board.take_delta());
+        outcome.is_exploded()
+    }
+
+    /// check if the move is played on a cell, undo and redo have none
+    pub fn has_cell(&self) -> bool {
+        !matches!(self.action, Action::Undo | Action::Redo)
+    }
+
+    /// a line of the file: the time, the action and the cell
+    pub fn get_line(&self) -> String {
+        format!("{} {} {} {}", self.ms, self.action.get_name(), self.x, self.y)
+    }
+
+    /// read a line of the file, returns None for an invalid line
+    pub fn from_line(line: &str) -> Option<Move> {
+        let mut columns = line.split(' ');
+        Some(Move {
+            ms: columns.next()?.parse().ok()?,
+            action: Action::from_name(columns.next()?)?,
+            x: columns.next()?.parse().ok()?,
+            y: columns.next()?.parse().ok()?,
+        })
+    }
+
+}
+
+/// all moves of a game, with the bombs and the settings the board can be created again
+#[derive(Clone,Debug,Default)]
+pub struct Replay {
+    pub settings: Settings,
+    pub seed: u64,
+    pub bombs: Vec<usize>,
+    pub moves: Vec<Move>,
+}
+
+impl Replay {
+
+    /// start the recording of a new board
+    pub fn new(settings: Settings, seed: u64) -> Replay {
+        Replay { settings, seed, bombs: Vec::new(), moves: Vec::new() }
+    }
+
+    /// check if there is a replay of the last game
+    pub fn exists() -> bool {
+        storage::exists(REPLAY_FILE)
+    }
+
+    /// read the replay of the last game, returns None if there is none or if it can't be read
+    pub fn load() -> Option<Replay> {
+        let text = storage::read(REPLAY_FILE).ok()??;
+        Replay::from_text(&text).ok()
+    }
+
+    /// write the replay to the data directory, it replaces the replay of the last game
+    pub fn save(&self) -> std::io::Result<()> {
+        storage::write(REPLAY_FILE, &self.to_text())
+    }
+
+    /// create the board of the replay with its bombs, no move is played yet
+    pub fn create_board(&self) -> Board {
+        let mut board = self.settings.create_board();
+        board.set_seed(self.seed);
+        board.set_bombs(&self.bombs);
+        board
+    }
+
+    /// the file has the settings, the seed and the bombs at the beginning, a line for every move follows
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", HEADER, VERSION);
+        text.push_str(&self.settings.get_text());
+        text.push_str(&format!("seed {}\n", self.seed));
+        let bombs: Vec<String> = self.bombs.iter().map(|index| index.to_string()).collect();
+        text.push_str(&format!("bombs {}\n", bombs.join(" ")));
+        text.push_str("moves\n");
+        for mv in &self.moves {
+            text.push_str(&mv.get_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// read the text of a replay, returns an error message for an invalid file
+    pub fn from_text(text: &str) -> Result<Replay, String> {
+        let mut lines = text.lines();
+        // the boards of the first version were created with another generator, they can't be played again
+        let header = format!("{} {}", HEADER, VERSION);
+        if lines.next() != Some(header.as_str()) {
+            return Err(String::from("unknown file format"));
+        }
+        let values = Values::read(&mut lines, "moves")?;
+        let mut replay = Replay::new(Settings::from_values(&values)?, values.get("seed")?);
+        replay.bombs = values.get_str("bombs")?.split(' ')
+            .map(|index| index.parse().map_err(|_| format!("invalid bomb: {}", index)))
+            .collect::<Result<_, _>>()?;
+        if !replay.settings.create_board().set_bombs(&replay.bombs) {
+            return Err(String::from("invalid bombs"));
+        }
+        for line in lines {
+            let mv = Move::from_line(line).ok_or_else(|| format!("invalid move: {}", line))?;
+            replay.moves.push(mv);
+        }
+        Ok(replay)
+    }
+
+}
+
+/// the clock and the position of a running replay
+#[derive(Clone,Debug)]
+pub struct Playback {
+    pub replay: Replay,
+    pub history: History,
+    pub next: usize,
+    pub paused: bool,
+    ms: f64,
+    speed: usize,
+    clock: Instant,
+}
+
+impl Playback {
+
+    /// the playback starts at the beginning with the normal speed
+    pub fn new(replay: Replay) -> Playback {
+        Playback { replay, history: History::default(), next: 0, paused: false, ms: 0.0, speed: NORMAL_SPEED, clock: Instant::now() }
+    }
+
+    /// move the clock on, returns the next move if its time has come
+    pub fn next_move(&mut self) -> Option<Move> {
+        let now = Instant::now();
+        if !self.paused {
+            self.ms += now.duration_since(self.clock).as_secs_f64() * 1_000.0 * SPEEDS[self.speed];
+        }
+        self.clock = now;
+        let mv = *self.replay.moves.get(self.next)?;
+        if mv.ms as f64 > self.ms {
+            return None;
+        }
+        self.next += 1;
+        Some(mv)
+    }
+
+    /// check if all moves are played
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.moves.len()
+    }
+
+    /// the game time of the playback
+    pub fn get_ms(&self) -> u64 {
+        self.ms as u64
+    }
+
+    /// the playback continues at the time of the last played move
+    pub fn set_next(&mut self, next: usize) {
+        self.next = next.min(self.replay.moves.len());
+        self.ms = match self.next {
+            0 => 0.0,
+            n => self.replay.moves[n - 1].ms as f64,
+        };
+        self.clock = Instant::now();
+    }
+
+    /// get the current speed factor
+    pub fn get_speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    /// play faster or slower
+    pub fn change_speed(&mut self, faster: bool) {
+        if faster {
+            self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+        } else {
+            self.speed = self.speed.saturating_sub(1);
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::BoardSize;
+
+    /// a recorded game with a reveal, a flag and a chord
+    fn create_replay() -> Replay {
+        let settings = Settings { board_size: BoardSize::Custom { width: 12, height: 8, mines: 15 }, no_guess: true, ..Settings::default() };
+        let mut board = settings.create_board();
+        board.set_seed(7);
+        board.reveal(6, 4);
+        let mut replay = Replay::new(settings, board.get_seed());
+        replay.bombs = board.get_bombs();
+        replay.moves = vec![
+            Move { ms: 0, action: Action::Reveal, x: 6, y: 4 },
+            Move { ms: 1_250, action: Action::Flag, x: 0, y: 7 },
+            Move { ms: 2_000, action: Action::Chord, x: 6, y: 4 },
+        ];
+        replay
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let replay = create_replay();
+        let loaded = Replay::from_text(&replay.to_text()).unwrap();
+        assert_eq!(loaded.settings, replay.settings);
+        assert_eq!(loaded.seed, replay.seed);
+        assert_eq!(loaded.bombs, replay.bombs);
+        assert_eq!(loaded.moves, replay.moves);
+    }
+
+    #[test]
+    fn playback_creates_the_same_board() {
+        let replay = create_replay();
+        let mut board = replay.settings.create_board();
+        board.set_seed(replay.seed);
+        let mut played = replay.create_board();
+        let mut history = History::default();
+        let mut played_history = History::default();
+        for mv in &replay.moves {
+            assert_eq!(mv.apply(&mut played, &mut played_history), mv.apply(&mut board, &mut history));
+        }
+        assert_eq!(played.get_cells_text(), board.get_cells_text());
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        let mut replay = create_replay();
+        replay.bombs = (0..15).collect();
+        let text = replay.to_text();
+        assert!(Replay::from_text(&text.replace("replay 2", "replay 1")).is_err());
+        assert!(Replay::from_text(&text.replace("seed 7", "seed x")).is_err());
+        assert!(Replay::from_text(&text.replace("bombs 0 1", "bombs 0 0")).is_err());
+        assert!(Replay::from_text(&format!("{}0 jump 1 1\n", text)).is_err());
+    }
+
+    #[test]
+    fn playback_after_undo_of_the_first_move() {
+        let settings = Settings::default();
+        let moves = [
+            Move { ms: 0, action: Action::Reveal, x: 4, y: 4 },
+            Move { ms: 500, action: Action::Undo, x: 0, y: 0 },
+            Move { ms: 900, action: Action::Reveal, x: 0, y: 8 },
+        ];
+        let mut board = settings.create_board();
+        let mut history = History::default();
+        board.set_seed(11);
+        let boards: Vec<String> = moves.iter().map(|mv| {
+            mv.apply(&mut board, &mut history);
+            board.get_cells_text()
+        }).collect();
+        let mut replay = Replay::new(settings, board.get_seed());
+        replay.bombs = board.get_bombs();
+        replay.moves = moves.to_vec();
+
+        // every step of the playback shows the board of the game
+        let mut played = replay.create_board();
+        let mut history = History::default();
+        for (mv, cells) in replay.moves.iter().zip(&boards) {
+            mv.apply(&mut played, &mut history);
+            assert_eq!(&played.get_cells_text(), cells);
+        }
+        assert_eq!(played.get_state(), board.get_state());
+    }
+}
@@ -0,0 +1,178 @@
+// part of the Minesweeper game for the terminal
+use crate::game::board::Board;
+use crate::game::replay::Move;
+use crate::game::settings::Settings;
+use crate::game::storage::{self, Values};
+
+/// the file of the interrupted game in the data directory
+const SAVE_FILE: &str = "save.txt";
+
+/// the first line of the file, the version changes with every new format
+const HEADER: &str = "minesweeper save";
+const VERSION: u32 = 2;
+
+/// an interrupted game, that can be continued later
+#[derive(Clone,Debug)]
+pub struct SaveGame {
+    pub settings: Settings,
+    pub board: Board,
+    pub seconds: u64,
+    pub cursor: (u16, u16),
+    pub moves: Vec<Move>,
+    pub hints: u32,
+}
+
+impl SaveGame {
+
+    /// check if there is a saved game
+    pub fn exists() -> bool {
+        storage::exists(SAVE_FILE)
+    }
+
+    /// read the saved game, returns None if there is none or if it can't be read
+    pub fn load() -> Option<SaveGame> {
+        let text = storage::read(SAVE_FILE).ok()??;
+        SaveGame::from_text(&text).ok()
+    }
+
+    /// write the game to the data directory
+    pub fn save(&self) -> std::io::Result<()> {
+        storage::write(SAVE_FILE, &self.to_text())
+    }
+
+    /// the saved game is removed, when it was continued or the game is over
+    pub fn remove() {
+        // a game that can't be removed is continued again, nothing more to do here
+        let _ = storage::remove(SAVE_FILE);
+    }
+
+    /// the file has a value per line, then the cells with a line for every row
+    /// and the moves for the replay at the end
+    pub fn to_text(&self) -> String {
+        let (x, y) = self.cursor;
+        let mut text = format!("{} {}\n", HEADER, VERSION);
+        text.push_str(&self.settings.get_text());
+        text.push_str(&format!("seed {}\n", self.board.get_seed()));
+        text.push_str(&format!("seconds {}\n", self.seconds));
+        text.push_str(&format!("hints {}\n", self.hints));
+        text.push_str(&format!("cursor {} {}\n", x, y));
+        if let Some((x, y)) = self.board.get_start() {
+            text.push_str(&format!("start {} {}\n", x, y));
+        }
+        text.push_str("cells\n");
+        text.push_str(&self.board.get_cells_text());
+        text.push_str("moves\n");
+        for mv in &self.moves {
+            text.push_str(&mv.get_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// read the text of a saved game, returns an error message for an invalid file
+    pub fn from_text(text: &str) -> Result<SaveGame, String> {
+        let mut lines = text.lines();
+        let version = lines.next()
+            .and_then(|line| line.strip_prefix(HEADER))
+            .and_then(|version| version.strip_prefix(' '))
+            .and_then(|version| version.parse::<u32>().ok())
+            .filter(|version| (1..=VERSION).contains(version))
+            .ok_or("unknown file format")?;
+
+        let values = Values::read(&mut lines, "cells")?;
+        let settings = Settings::from_values(&values)?;
+        let (x, y) = values.get_str("cursor")?
+            .split_once(' ')
+            .and_then(|(x, y)| Some((x.parse::<u16>().ok()?, y.parse::<u16>().ok()?)))
+            .ok_or("invalid cursor")?;
+
+        let mut board = settings.create_board();
+        board.set_seed(values.get("seed")?);
+        let cells: Vec<&str> = lines.by_ref().take(board.get_height() as usize).collect();
+        if !board.set_cells_text(&cells.join("\n")) {
+            return Err(String::from("invalid cells"));
+        }
+        if x >= board.get_width() || y >= board.get_height() {
+            return Err(String::from("invalid cursor"));
+        }
+        // older files have no start
+        if let Some((x, y)) = values.get_str("start").ok()
+            .and_then(|start| start.split_once(' '))
+            .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+            .filter(|&(x, y)| x < board.get_width() as usize && y < board.get_height() as usize) {
+            board.set_start(x, y);
+        }
+
+        // the first version has no moves, the replay starts with the continued game
+        let mut moves = Vec::new();
+        if version >= 2 && lines.next() != Some("moves") {
+            return Err(String::from("missing moves"));
+        }
+        for line in lines {
+            moves.push(Move::from_line(line).ok_or_else(|| format!("invalid move: {}", line))?);
+        }
+
+        // older files have no hints
+        let hints = values.get("hints").unwrap_or(0);
+        Ok(SaveGame { settings, board, seconds: values.get("seconds")?, cursor: (x, y), moves, hints })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::BoardSize;
+    use crate::game::history::History;
+    use crate::game::replay::Action;
+
+    /// a game after the first pick and a flag
+    fn create_game() -> SaveGame {
+        let settings = Settings { board_size: BoardSize::Medium, no_guess: true, ..Settings::default() };
+        let moves = vec![
+            Move { ms: 0, action: Action::Reveal, x: 10, y: 10 },
+            Move { ms: 900, action: Action::Flag, x: 0, y: 0 },
+        ];
+        let mut board = settings.create_board();
+        board.set_seed(3);
+        let mut history = History::default();
+        for mv in &moves {
+            mv.apply(&mut board, &mut history);
+        }
+        SaveGame { settings, board, seconds: 42, cursor: (3, 4), moves, hints: 2 }
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let game = create_game();
+        let loaded = SaveGame::from_text(&game.to_text()).unwrap();
+        assert_eq!(loaded.settings, game.settings);
+        assert_eq!(loaded.board.get_cells_text(), game.board.get_cells_text());
+        assert_eq!(loaded.board.get_seed(), game.board.get_seed());
+        assert_eq!(loaded.seconds, 42);
+        assert_eq!(loaded.cursor, (3, 4));
+        assert_eq!(loaded.moves, game.moves);
+        assert_eq!(loaded.hints, 2);
+        // older files have no hints
+        let loaded = SaveGame::from_text(&game.to_text().replace("hints 2\n", "")).unwrap();
+        assert_eq!(loaded.hints, 0);
+    }
+
+    #[test]
+    fn first_version_has_no_moves() {
+        let text = create_game().to_text().replace("save 2", "save 1");
+        let text = &text[..text.find("moves\n").unwrap()];
+        let loaded = SaveGame::from_text(text).unwrap();
+        assert!(loaded.moves.is_empty());
+        assert_eq!(loaded.board.get_cells_text(), create_game().board.get_cells_text());
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        let text = create_game().to_text();
+        assert!(SaveGame::from_text(&text.replace("save 2", "save 3")).is_err());
+        assert!(SaveGame::from_text(&text.replace("cursor 3 4", "cursor 3 20")).is_err());
+        assert!(SaveGame::from_text(&text.replace("width 20", "width 19")).is_err());
+        assert!(SaveGame::from_text(&text.replace("900 flag", "900 jump")).is_err());
+    }
+}
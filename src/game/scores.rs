@@ -0,0 +1,180 @@
+// part of the Minesweeper game for the terminal
+use crate::game::storage;
+
+/// the file of the high scores in the data directory
+const SCORES_FILE: &str = "scores.txt";
+
+/// the number of scores, that are kept for every board size
+pub const MAX_SCORES: usize = 10;
+
+/// the longest name, that can be entered for a high score
+pub const MAX_NAME_LEN: usize = 16;
+
+/// the first line of the file explains the columns
+const HEADER: &str = "# minesweeper high scores: width height mines seconds seed hints name";
+
+/// a won game in the high score table
+#[derive(Clone,Debug,PartialEq)]
+pub struct HighScore {
+    pub width: u16,
+    pub height: u16,
+    pub mines: u32,
+    pub seconds: u64,
+    pub seed: u64,
+    pub hints: u32,
+    pub name: String,
+}
+
+impl HighScore {
+
+    /// check if the score was played on a board of the given size
+    pub fn is_size(&self, width: u16, height: u16, mines: u32) -> bool {
+        self.width == width && self.height == height && self.mines == mines
+    }
+
+    /// a line of the file, the columns are separated by tabs and the name is the last column
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}\t{}\t{}", self.width, self.height, self.mines, self.seconds, self.seed, self.hints, self.name)
+    }
+
+    /// read a line of the file, returns None for invalid lines.
+    /// The lines of older files have no hints
+    fn from_line(line: &str) -> Option<HighScore> {
+        let columns: Vec<&str> = line.split('\t').collect();
+        let (hints, name) = match columns.len() {
+            6 => (0, columns[5]),
+            7 => (columns[5].parse().ok()?, columns[6]),
+            _ => return None,
+        };
+        Some(HighScore {
+            width: columns[0].parse().ok()?,
+            height: columns[1].parse().ok()?,
+            mines: columns[2].parse().ok()?,
+            seconds: columns[3].parse().ok()?,
+            seed: columns[4].parse().ok()?,
+            hints,
+            name: name.to_string(),
+        })
+    }
+
+}
+
+/// the high score tables of all board sizes, the best time comes first
+#[derive(Clone,Debug,Default)]
+pub struct HighScores {
+    scores: Vec<HighScore>,
+}
+
+impl HighScores {
+
+    /// read the high scores from the data directory, invalid lines are skipped
+    pub fn load() -> HighScores {
+        let text = storage::read(SCORES_FILE).ok().flatten().unwrap_or_default();
+        let mut high_scores = HighScores::default();
+        for line in text.lines() {
+            if line.starts_with('#') { continue; }
+            if let Some(score) = HighScore::from_line(line) {
+                high_scores.scores.push(score);
+            }
+        }
+        high_scores.scores.sort_by_key(|score| score.seconds);
+        high_scores
+    }
+
+    /// write all high scores to the data directory
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+        for score in &self.scores {
+            text.push_str(&score.to_line());
+            text.push('\n');
+        }
+        storage::write(SCORES_FILE, &text)
+    }
+
+    /// get the table of a board size
+    pub fn get_table(&self, width: u16, height: u16, mines: u32) -> Vec<&HighScore> {
+        self.scores.iter().filter(|score| score.is_size(width, height, mines)).collect()
+    }
+
+    /// get all board sizes with scores, the smallest board comes first
+    pub fn get_sizes(&self) -> Vec<(u16, u16, u32)> {
+        let mut sizes: Vec<(u16, u16, u32)> = self.scores.iter()
+            .map(|score| (score.width, score.height, score.mines))
+            .collect();
+        sizes.sort_by_key(|&(width, height, mines)| (width as u32 * height as u32, mines, width));
+        sizes.dedup();
+        sizes
+    }
+
+    /// get the rank of a time in the table of a board size,
+    /// returns None if the time is not good enough for the table
+    pub fn get_rank(&self, width: u16, height: u16, mines: u32, seconds: u64) -> Option<usize> {
+        let table = self.get_table(width, height, mines);
+        let rank = table.iter().filter(|score| score.seconds <= seconds).count();
+        if rank < MAX_SCORES { Some(rank) } else { None }
+    }
+
+    /// add a score to its table, the slowest score is dropped from a full table
+    pub fn add(&mut self, score: HighScore) -> Option<usize> {
+        let rank = self.get_rank(score.width, score.height, score.mines, score.seconds)?;
+        let pos = self.scores.iter().filter(|s| s.seconds <= score.seconds).count();
+        self.scores.insert(pos, score.clone());
+        let mut count = 0;
+        self.scores.retain(|s| {
+            if !s.is_size(score.width, score.height, score.mines) { return true; }
+            count += 1;
+            count <= MAX_SCORES
+        });
+        Some(rank)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a score on the small board
+    fn create_score(seconds: u64, name: &str) -> HighScore {
+        HighScore { width: 10, height: 10, mines: 10, seconds, seed: 7, hints: 1, name: name.to_string() }
+    }
+
+    #[test]
+    fn add_sorts_by_the_time() {
+        let mut high_scores = HighScores::default();
+        assert_eq!(high_scores.add(create_score(50, "b")), Some(0));
+        assert_eq!(high_scores.add(create_score(20, "a")), Some(0));
+        // the same time comes after the older score
+        assert_eq!(high_scores.add(create_score(50, "c")), Some(2));
+        let names: Vec<&str> = high_scores.get_table(10, 10, 10).iter().map(|score| score.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert!(high_scores.get_table(20, 20, 40).is_empty());
+    }
+
+    #[test]
+    fn full_table_drops_the_slowest() {
+        let mut high_scores = HighScores::default();
+        for seconds in 1..=MAX_SCORES as u64 {
+            high_scores.add(create_score(seconds * 10, "x"));
+        }
+        assert_eq!(high_scores.get_rank(10, 10, 10, 1_000), None);
+        assert_eq!(high_scores.add(create_score(1_000, "slow")), None);
+        assert_eq!(high_scores.get_rank(10, 10, 10, 5), Some(0));
+        assert_eq!(high_scores.add(create_score(5, "fast")), Some(0));
+        let table = high_scores.get_table(10, 10, 10);
+        assert_eq!(table.len(), MAX_SCORES);
+        assert_eq!(table[MAX_SCORES - 1].seconds, 90);
+        // another board size has its own table
+        assert_eq!(high_scores.get_rank(20, 20, 40, 1_000), Some(0));
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let score = create_score(42, "name with spaces");
+        assert_eq!(HighScore::from_line(&score.to_line()), Some(score));
+        assert_eq!(HighScore::from_line("10\t10\tx\t42\t7\tname"), None);
+        // the lines of older files have no hints
+        assert_eq!(HighScore::from_line("10\t10\t10\t42\t7\tname").map(|score| score.hints), Some(0));
+    }
+}
@@ -0,0 +1,97 @@
+// part of the Minesweeper game for the terminal
+use crate::game::board::{BoardSize, MAX_BOARD_WIDTH, MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH};
+use crate::game::game_struct::Game;
+use crate::term::Term;
+use crossterm::event;
+
+/// the first line of the input fields below the start menu
+const Y_POS: u16 = 15;
+
+/// the input fields for a custom board: width, height and mines
+#[derive(Clone,Debug,Default)]
+pub struct CustomBoardForm {
+    field: usize,
+    input: String,
+    values: [u32; 3],
+    error: bool,
+}
+
+impl CustomBoardForm {
+
+    /// the label and the valid range of the given field, a wide board can't be as high
+    fn get_field(&self, field: usize) -> (String, u32, u32) {
+        match field {
+            0 => (format!("Width  ({}-{}): ", MIN_BOARD_WIDTH, MAX_BOARD_WIDTH), MIN_BOARD_WIDTH as u32, MAX_BOARD_WIDTH as u32),
+            1 => {
+                let max_height = BoardSize::max_height(self.values[0] as usize);
+                (format!("Height ({}-{}): ", MIN_BOARD_HEIGHT, max_height), MIN_BOARD_HEIGHT as u32, max_height as u32)
+            }
+            _ => {
+                let max_mines = BoardSize::max_mines(self.values[0] as usize, self.values[1] as usize);
+                (format!("Mines  (1-{}): ", max_mines), 1, max_mines)
+            }
+        }
+    }
+
+}
+
+/// the input fields are shown below the start menu
+pub fn draw(term: &mut Term, game: &Game) {
+
+    let form = &game.custom_form;
+
+    term.print_xy(1, Y_POS, "Classic layouts are 9x9 with 10, 16x16 with 40 and 30x16 with 99 mines.");
+    term.print_xy(1, Y_POS + 1, "Confirm every value with ENTER or press ESC to go back.");
+
+    let mut cursor = (0, 0);
+    for field in 0..=form.field {
+        let (label, min, max) = form.get_field(field);
+        let y = Y_POS + 3 + field as u16;
+        let value = if field == form.field { form.input.clone() } else { form.values[field].to_string() };
+        term.print_xy(1, y, &format!("{}{:10}", label, value));
+        cursor = (1 + (label.len() + value.len()) as u16, y);
+        if field == form.field && form.error {
+            term.print_xy(1, y + 1, &format!("\x1b[31mPlease enter a number between {} and {}.\x1b[0m", min, max));
+        }
+    }
+
+    term.move_xy(cursor.0, cursor.1);
+    term.show_cursor();
+
+}
+
+/// a simple input field for numbers, the value must be in the valid range of the field
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    let form = &mut game.custom_form;
+
+    match key.code {
+        event::KeyCode::Char(c) if c.is_ascii_digit() && form.input.len() < 10 => form.input.push(c),
+        event::KeyCode::Backspace => { form.input.pop(); },
+        event::KeyCode::Esc => {
+            game.pop_gamestate();
+            return;
+        }
+        event::KeyCode::Enter => {
+            let (_, min, max) = form.get_field(form.field);
+            match form.input.parse::<u32>() {
+                Ok(n) if n >= min && n <= max => {
+                    form.values[form.field] = n;
+                    form.field += 1;
+                    form.input.clear();
+                    form.error = false;
+                }
+                _ => form.error = true,
+            }
+        }
+        _ => {}
+    }
+
+    if game.custom_form.field == 3 {
+        let [width, height, mines] = game.custom_form.values;
+        game.settings.board_size = BoardSize::Custom { width: width as u16, height: height as u16, mines };
+        game.new_game();
+    }
+    game.update = true;
+
+}
@@ -0,0 +1,95 @@
+// part of the Minesweeper game for the terminal
+use crate::game::game_struct::{Game,GameState};
+use crate::game::scores::MAX_NAME_LEN;
+use crate::term::Term;
+use crossterm::event;
+
+/// end of game screen
+pub fn draw(term: &mut Term, game: &Game) {
+
+    let end_msg = match game.get_gamestate() {
+        GameState::Loose => "Boom, you lost!",
+        GameState::Win => "Congratulations, you won!",
+        _ => "",
+    };
+
+    let (correct_flags, wrong_flags) = game.get_flag_count();
+    let mut lines = vec![
+        String::from(end_msg),
+        format!("Flags: {} correct, {} wrong", correct_flags, wrong_flags),
+        format!("Hints: {}", game.get_hints()),
+        format!("Seed: {}", game.get_seed_text()),
+    ];
+    let (width, height, mines) = game.get_board_size();
+    if let Some(s) = game.stats.get_size(width, height, mines) {
+        lines.push(format!("Played {}, won {} ({:.0}%), streak {}, best {}", s.played, s.won, s.get_win_rate(), s.streak, s.longest_streak));
+    }
+    if game.score_name.is_some() {
+        lines.push(String::new());
+        lines.push(format!("New high score in {}!", game.get_formated_seconds()));
+        lines.push(format!("Your name: {:width$}", "", width = MAX_NAME_LEN));
+    } else if game.can_undo_loss() {
+        lines.push(String::from("Press u to undo the last move."));
+    } else {
+        lines.push(String::from("Press v for the replay, o for the mine chances."));
+    }
+    if let Some(text) = game.get_chance_text() && game.score_name.is_none() {
+        lines.push(text);
+    }
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0) as u16;
+
+    // the box stays on the screen, even if a line is wider than the terminal
+    let mut x = game.get_term_width().saturating_sub(width) / 2;
+    let mut y = game.get_term_height().saturating_sub(lines.len() as u16) / 2;
+
+    term.print_box(x.saturating_sub(1), y - 1, width + 2, lines.len() as u16 + 2);
+    for line in lines.iter() {
+        term.print_xy(x + (width - line.len() as u16) / 2, y, line);
+        y += 1;
+    }
+
+    // the terminal cursor shows the name input in the last line of the box
+    if let Some(name) = &game.score_name {
+        let label = lines.last().map(|line| line.len()).unwrap_or(0) - MAX_NAME_LEN;
+        let name_x = x + (width - lines[lines.len() - 1].len() as u16) / 2 + label as u16;
+        let rpl_msg = "Enter your name and press ENTER to save it or ESC to skip.";
+        term.print_xy(game.get_term_width().saturating_sub(rpl_msg.len() as u16) / 2, game.get_board_y() + game.get_view_height() + 3, rpl_msg);
+        term.print_xy(name_x, y - 1, name);
+        term.move_xy(name_x + name.chars().count() as u16, y - 1);
+        term.show_cursor();
+        return;
+    }
+
+    let rpl_msg = "Do you want to \x1b[32ms\x1b[0mtart a new game, go to the \x1b[32mm\x1b[0menu or to \x1b[32mq\x1b[0muit?";
+    x = game.get_term_width().saturating_sub(rpl_msg.len() as u16 - 27) / 2;
+    y = (game.get_board_y() + game.get_view_height()) + 3;
+    term.print_xy(x, y, rpl_msg);
+
+}
+
+/// start a new game or leave it, a new high score asks for the name first
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    if let Some(name) = &mut game.score_name {
+        match key.code {
+            event::KeyCode::Char(c) if !c.is_control() && name.chars().count() < MAX_NAME_LEN => name.push(c),
+            event::KeyCode::Backspace => { name.pop(); },
+            event::KeyCode::Enter => game.save_high_score(),
+            event::KeyCode::Esc => game.score_name = None,
+            _ => return,
+        }
+        game.update = true;
+        return;
+    }
+
+    match key.code {
+        event::KeyCode::Char('s') => game.reset_board(),
+        event::KeyCode::Char('m') => game.show_menu(),
+        event::KeyCode::Char('v') => game.start_playback(),
+        event::KeyCode::Char('u') => game.undo_move(),
+        event::KeyCode::Char('o') => game.toggle_analysis(),
+        event::KeyCode::Char('q') | event::KeyCode::F(10) => game.set_gamestate(GameState::GiveUp),
+        _ => {}
+    }
+
+}
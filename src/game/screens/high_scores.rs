@@ -0,0 +1,48 @@
+// part of the Minesweeper game for the terminal
+use crate::game::GAME_NAME;
+use crate::game::game_struct::{format_seconds, Game};
+use crate::term::Term;
+use crossterm::event;
+
+/// the high scores of one board size, the player switches between the sizes
+pub fn draw(term: &mut Term, game: &Game) {
+
+    term.cls();
+    term.print_xy(1, 1, format!("{} - High scores", GAME_NAME).as_str());
+
+    let sizes = game.high_scores.get_sizes();
+    if sizes.is_empty() {
+        term.print_xy(1, 3, "There are no high scores yet, win a game to get one.");
+        term.print_xy(1, 5, "Press ESC or ENTER to go back.");
+        return;
+    }
+
+    let page = game.score_page % sizes.len();
+    let (width, height, mines) = sizes[page];
+    term.print_xy(1, 3, format!("Board {}x{} with {} mines ({}/{})", width, height, mines, page + 1, sizes.len()).as_str());
+    term.print_xy(1, 5, format!("\x1b[1m{:>4}  {:<16}  {:>9}  {:>5}  {}\x1b[0m", "Rank", "Name", "Time", "Hints", "Seed").as_str());
+
+    let table = game.high_scores.get_table(width, height, mines);
+    for (rank, score) in table.iter().enumerate() {
+        let line = format!("{:>4}  {:<16}  {:>9}  {:>5}  {}", rank + 1, score.name, format_seconds(score.seconds), score.hints, score.seed);
+        term.print_xy(1, 6 + rank as u16, &line);
+    }
+
+    term.print_xy(1, 7 + table.len() as u16, "Press LEFT or RIGHT for the other board sizes, ESC or ENTER to go back.");
+
+}
+
+/// switch between the board sizes
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    let sizes = game.high_scores.get_sizes().len().max(1);
+
+    match key.code {
+        event::KeyCode::Left => game.score_page = (game.score_page + sizes - 1) % sizes,
+        event::KeyCode::Right => game.score_page = (game.score_page + 1) % sizes,
+        event::KeyCode::Esc | event::KeyCode::Enter => game.pop_gamestate(),
+        _ => return,
+    }
+    game.update = true;
+
+}
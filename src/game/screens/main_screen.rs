@@ -0,0 +1,235 @@
+// part of the Minesweeper game for the terminal
+use crate::game::game_struct::{Game,GameState};
+use crate::term::Term;
+use crossterm::event;
+
+/// this is the main screen to show the minefield
+pub fn draw(term: &mut Term, game: &Game) {
+
+    term.cls();
+    term.print_xy(0, 0, game.get_title());
+
+    term.print_box(
+        game.get_board_x() - 1,
+        game.get_board_y() - 1,
+        game.get_view_width() + 2,
+        game.get_view_height() + 2
+    );
+
+    term.print_xy(game.get_seconds_x(), game.get_seconds_y(), &game.get_seconds_text());
+    term.print_xy(game.get_bombs_x(), game.get_bombs_y(), &game.get_bombs_text());
+
+    let mut x_pos = game.get_board_x();
+    let mut y_pos = game.get_board_y();
+    for line in game.get_board_gfx() {
+        term.print_xy(x_pos, y_pos, line.as_str());
+        y_pos += 1;
+    }
+
+    draw_scrollbars(term, game);
+    y_pos += 1;
+
+    let rules_text = "\x1b[32;100m F1 \x1b[97;100m Rules \x1b[0m";
+    let quit_text =  "\x1b[32;100m F10\x1b[97;100m Quit  \x1b[0m";
+    x_pos = game.get_term_width() / 2;
+    term.print_xy(x_pos - 15, y_pos + 1, rules_text);
+    term.print_xy(x_pos + 5, y_pos + 1, quit_text);
+
+    // the hint is explained below the footer, otherwise the analysis shows the cell under the cursor
+    // or the warning of the no guess mode
+    if let Some((_, text)) = game.get_hint() {
+        let len = text.chars().count() as u16;
+        term.print_xy(game.get_term_width().saturating_sub(len) / 2, y_pos + 2, text);
+    } else if let Some(text) = game.get_chance_text() {
+        term.print_xy(game.get_term_width().saturating_sub(text.len() as u16) / 2, y_pos + 2, &text);
+    } else if let Some(text) = game.get_guess_warning() {
+        term.print_xy(game.get_term_width().saturating_sub(text.len() as u16) / 2, y_pos + 2, text);
+    }
+
+}
+
+/// show the scroll indicators next to the frame, if the board doesn't fit into the terminal
+fn draw_scrollbars(term: &mut Term, game: &Game) {
+
+    let view = game.get_viewport();
+    let x_pos = game.get_board_x();
+    let y_pos = game.get_board_y();
+
+    if view.is_scrollable_y() {
+        let (start, len) = view.get_thumb_y();
+        for i in 0..view.get_height() {
+            let gfx = if i >= start && i < start + len { "█" } else { "\x1b[90m░\x1b[0m" };
+            term.print_xy(x_pos + view.get_width() + 1, y_pos + i, gfx);
+        }
+    }
+
+    if view.is_scrollable_x() {
+        let (start, len) = view.get_thumb_x();
+        for i in 0..view.get_width() {
+            let gfx = if i >= start && i < start + len { "█" } else { "\x1b[90m░\x1b[0m" };
+            term.print_xy(x_pos + i, y_pos + view.get_height() + 1, gfx);
+        }
+    }
+
+}
+
+/// play with the keyboard
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    // a number before a cursor move repeats it, like in vi
+    if let event::KeyCode::Char(c @ '0'..='9') = key.code {
+        game.push_move_count(c as u32 - '0' as u32);
+        return;
+    }
+
+    // move the keyboard cursor, SHIFT jumps to the edge of the board
+    if let Some((dx, dy, to_edge)) = cursor_direction(key) {
+        let count = game.take_move_count();
+        if to_edge {
+            game.move_cursor(dx * u16::MAX as i32, dy * u16::MAX as i32);
+        } else {
+            game.move_cursor(dx * count, dy * count);
+        }
+        return;
+    }
+    game.take_move_count();
+
+    let (cell_x, cell_y) = game.get_cursor();
+
+    match key.code {
+
+        // show the rules screen, the timer stops
+        event::KeyCode::F(1) => game.push_gamestate(GameState::Rules),
+
+        // exit the loop, to quit the game
+        event::KeyCode::F(10) | event::KeyCode::Char('q') => game.set_gamestate(GameState::GiveUp),
+
+        event::KeyCode::Char('p') => game.push_gamestate(GameState::Pause),
+
+        // play with the keyboard at the cursor
+        event::KeyCode::Char(' ') | event::KeyCode::Enter => {
+            let exploded = game.pick_board_cell(cell_x, cell_y);
+            game.check_end_of_game(exploded);
+        }
+        event::KeyCode::Char('c') => {
+            let exploded = game.chord_board_cell(cell_x, cell_y);
+            game.check_end_of_game(exploded);
+        }
+        event::KeyCode::Char('f') => {
+            game.flag_board_cell(cell_x, cell_y);
+            game.check_end_of_game(false);
+        }
+        event::KeyCode::Char('?') => {
+            game.question_board_cell(cell_x, cell_y);
+            game.update = true;
+        }
+
+        // show a safe cell or a mine, that can be found with logic
+        event::KeyCode::Char('i') => game.request_hint(),
+
+        // show the chance of every covered cell to be a mine
+        event::KeyCode::Char('o') => game.toggle_analysis(),
+
+        // take back a move or play it again
+        event::KeyCode::Char('u') => game.undo_move(),
+        event::KeyCode::Char('r') => {
+            let exploded = game.redo_move();
+            game.check_end_of_game(exploded);
+        }
+
+        _ => {}
+    }
+}
+
+/// play with the mouse
+pub fn handle_mouse(game: &mut Game, mouse_event: &event::MouseEvent) {
+
+    match mouse_event.kind {
+
+        // the mouse wheel scrolls the viewport, with SHIFT horizontal
+        event::MouseEventKind::ScrollUp | event::MouseEventKind::ScrollDown => {
+            let step = if mouse_event.kind == event::MouseEventKind::ScrollUp { -3 } else { 3 };
+            if mouse_event.modifiers.contains(event::KeyModifiers::SHIFT) {
+                game.scroll_view(step, 0);
+            } else {
+                game.scroll_view(0, step);
+            }
+        }
+        event::MouseEventKind::ScrollLeft => game.scroll_view(-3, 0),
+        event::MouseEventKind::ScrollRight => game.scroll_view(3, 0),
+
+        _ => {
+            // remember the pressed buttons, left and right together chord a number
+            let mut chord = false;
+            match mouse_event.kind {
+                event::MouseEventKind::Down(event::MouseButton::Left) => {
+                    chord = game.mouse_right;
+                    game.mouse_left = true;
+                }
+                event::MouseEventKind::Down(event::MouseButton::Right) => {
+                    chord = game.mouse_left;
+                    game.mouse_right = true;
+                }
+                event::MouseEventKind::Down(event::MouseButton::Middle) => chord = true,
+                _ => {}
+            }
+
+            // ignore all clicks outside of the visible board
+            let Some((cell_x, cell_y)) = game.get_board_cell(mouse_event.column, mouse_event.row) else { return; };
+
+            // the keyboard cursor follows the clicks
+            if matches!(mouse_event.kind, event::MouseEventKind::Down(_)) {
+                game.set_cursor(cell_x, cell_y);
+            }
+
+            // process middle click or both buttons
+            if chord {
+                // if chord_cell() is true, a flag was wrong and the player uncovered a bomb
+                let exploded = game.chord_board_cell(cell_x, cell_y);
+                game.check_end_of_game(exploded);
+
+            // process left click
+            } else if mouse_event.kind == event::MouseEventKind::Down(event::MouseButton::Left) {
+                // if pick_cell() is true, the player clicked a field with a bomb
+                let exploded = game.pick_board_cell(cell_x, cell_y);
+                game.check_end_of_game(exploded);
+
+            // process right click
+            } else if mouse_event.kind == event::MouseEventKind::Down(event::MouseButton::Right) {
+                game.mark_board_cell(cell_x, cell_y);
+                game.check_end_of_game(false);
+            }
+        }
+
+    }
+
+}
+
+/// get the direction of a cursor key and if the cursor should jump to the edge of the board,
+/// the arrow keys, hjkl and wasd are supported
+fn cursor_direction(event: &event::KeyEvent) -> Option<(i32,i32,bool)> {
+    let shift = event.modifiers.contains(event::KeyModifiers::SHIFT);
+    let direction = match event.code {
+        event::KeyCode::Left => (-1, 0, shift),
+        event::KeyCode::Right => (1, 0, shift),
+        event::KeyCode::Up => (0, -1, shift),
+        event::KeyCode::Down => (0, 1, shift),
+        event::KeyCode::Home => (-1, 0, true),
+        event::KeyCode::End => (1, 0, true),
+        event::KeyCode::PageUp => (0, -1, true),
+        event::KeyCode::PageDown => (0, 1, true),
+        event::KeyCode::Char(c) => match c {
+            'h' | 'a' => (-1, 0, false),
+            'l' | 'd' => (1, 0, false),
+            'k' | 'w' => (0, -1, false),
+            'j' | 's' => (0, 1, false),
+            'H' | 'A' => (-1, 0, true),
+            'L' | 'D' => (1, 0, true),
+            'K' | 'W' => (0, -1, true),
+            'J' | 'S' => (0, 1, true),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(direction)
+}
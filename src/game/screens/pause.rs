@@ -0,0 +1,37 @@
+// part of the Minesweeper game for the terminal
+use crate::game::game_struct::{Game,GameState};
+use crate::term::Term;
+use crossterm::event;
+
+/// the board is hidden while the game is paused, so the time can't be used to think
+pub fn draw(term: &mut Term, game: &Game) {
+
+    let x_pos = game.get_board_x();
+    let y_pos = game.get_board_y();
+    let blank = " ".repeat(game.get_view_width() as usize);
+    for y in 0..game.get_view_height() {
+        term.print_xy(x_pos, y_pos + y, &blank);
+    }
+
+    let pause_msg = "Paused, press \x1b[32mp\x1b[0m to continue";
+    let len = pause_msg.len() as u16 - 9;
+    let x = game.get_term_width().saturating_sub(len) / 2;
+    let y = game.get_term_height() / 2;
+    term.print_box(x.saturating_sub(1), y.saturating_sub(1), len + 2, 3);
+    term.print_xy(x, y, pause_msg);
+
+}
+
+/// continue the game or quit it
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    match key.code {
+        event::KeyCode::Char('p') | event::KeyCode::Esc => {
+            game.focus_pause = false;
+            game.pop_gamestate();
+        }
+        event::KeyCode::Char('q') | event::KeyCode::F(10) => game.set_gamestate(GameState::GiveUp),
+        _ => {}
+    }
+
+}
@@ -0,0 +1,77 @@
+// part of the Minesweeper game for the terminal
+use crate::game::game_struct::{Game,GameState};
+use crate::game::screens::main_screen;
+use crate::term::Term;
+use crossterm::event;
+
+/// the moves, that are skipped with PAGE UP and PAGE DOWN
+const SEEK_MOVES: usize = 10;
+
+/// the board of the replay is shown like a running game, the footer shows the playback
+pub fn draw(term: &mut Term, game: &Game) {
+
+    main_screen::draw(term, game);
+
+    let Some(playback) = &game.playback else { return; };
+    let state = if playback.is_finished() {
+        "end"
+    } else if playback.paused {
+        "paused"
+    } else {
+        "playing"
+    };
+    let mut status = format!("Replay: move {}/{}, speed {}x, {}",
+        playback.next, playback.replay.moves.len(), playback.get_speed(), state);
+    if let Some(text) = game.get_chance_text() {
+        status = format!("{} - {}", status, text);
+    }
+    let keys = "SPACE pause, \u{2190}\u{2192} step, PgUp/PgDn seek, +/- speed, o chances, ESC menu";
+
+    let y = game.get_board_y() + game.get_view_height() + 2;
+    term.clear_line(y);
+    term.print_xy(game.get_term_width().saturating_sub(status.len() as u16) / 2, y, &status);
+    term.print_xy(game.get_term_width().saturating_sub(keys.chars().count() as u16) / 2, y + 1, keys);
+
+}
+
+/// control the playback
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    let Some(playback) = &mut game.playback else { return; };
+    let next = playback.next;
+    let last = playback.replay.moves.len();
+
+    match key.code {
+        event::KeyCode::Char(' ') | event::KeyCode::Char('p') => {
+            // a finished replay starts again
+            if playback.is_finished() {
+                game.seek_playback(0);
+                if let Some(playback) = &mut game.playback { playback.paused = false; }
+            } else {
+                playback.paused = !playback.paused;
+            }
+        }
+        event::KeyCode::Char('+') => playback.change_speed(true),
+        event::KeyCode::Char('-') => playback.change_speed(false),
+        event::KeyCode::Char('o') => game.toggle_analysis(),
+        event::KeyCode::Right | event::KeyCode::Char('l') => step(game, next + 1),
+        event::KeyCode::Left | event::KeyCode::Char('h') => step(game, next.saturating_sub(1)),
+        event::KeyCode::PageDown => step(game, next + SEEK_MOVES),
+        event::KeyCode::PageUp => step(game, next.saturating_sub(SEEK_MOVES)),
+        event::KeyCode::Home => step(game, 0),
+        event::KeyCode::End => step(game, last),
+        event::KeyCode::Esc => game.show_menu(),
+        event::KeyCode::Char('q') | event::KeyCode::F(10) => game.set_gamestate(GameState::GiveUp),
+        _ => return,
+    }
+    game.update = true;
+
+}
+
+/// go to a move, the playback is paused there
+fn step(game: &mut Game, next: usize) {
+    game.seek_playback(next);
+    if let Some(playback) = &mut game.playback {
+        playback.paused = true;
+    }
+}
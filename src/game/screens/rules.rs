@@ -0,0 +1,88 @@
+// part of the Minesweeper game for the terminal
+use crate::game::game_struct::Game;
+use crate::term::Term;
+use crossterm::event;
+
+/// the text of the rules, it is scrolled if it doesn't fit into the terminal
+const RULES: [&str; 29] = [
+    "What is Minesweeper?",
+    "--------------------",
+    "Minesweeper is a game where mines are hidden in a grid of squares. Safe",
+    "squares have numbers telling you how many mines touch the square. You can use",
+    "the number clues to solve the game by opening all of the safe squares. If you",
+    "click on a mine you lose the game!",
+    "",
+    "How to play?",
+    "------------",
+    "You open squares with the left mouse button and put flags on mines with the",
+    "right mouse button. Pressing the right mouse button again changes your flag",
+    "into a questionmark. When you open a square that does not touch any mines, it",
+    "will be empty and the adjacent squares will automatically open in all",
+    "directions until reaching squares that contain numbers. Click a number with",
+    "the middle button, both buttons or again with the left button to open all",
+    "squares around it, if the number of flags around is right.",
+    "Without a mouse move the cursor with the arrow keys, hjkl or wasd. A number",
+    "before repeats the move, with SHIFT it jumps to the edge. SPACE opens a",
+    "square, f sets a flag, ? a questionmark and c opens all squares around a",
+    "number. u takes back your last move and r plays it again and i shows a hint.",
+    "In the practice mode o shows the chance of every square to be a mine.",
+    "",
+    "End of game",
+    "-----------",
+    "You won the game when all safe squares are open, if selected on the start",
+    "screen all mines must be correctly flagged too. Otherwise, if you click a",
+    "field with a bomb to open it you loose.",
+    "",
+    "Have fun and good luck!",
+];
+
+/// the lines of the rules, that fit between the title and the key line
+fn get_page_len(game: &Game) -> usize {
+    game.get_term_height().saturating_sub(4) as usize
+}
+
+/// the first line of the rules, that can be shown without a gap at the end
+fn get_last_start(game: &Game) -> usize {
+    RULES.len().saturating_sub(get_page_len(game))
+}
+
+/// show the rules
+pub fn draw(term: &mut Term, game: &Game) {
+
+    term.cls();
+    term.print_xy(0, 0, game.get_title());
+
+    let x_pos = 2;
+    let first = game.rules_line.min(get_last_start(game));
+    for (i, line) in RULES.iter().skip(first).take(get_page_len(game)).enumerate() {
+        term.print_xy(x_pos, 2 + i as u16, line);
+    }
+
+    let y_pos = game.get_term_height() - 1;
+    if get_last_start(game) > 0 {
+        term.print_xy(x_pos, y_pos, "Press \x1b[32mUP\x1b[0m or \x1b[32mDOWN\x1b[0m to scroll, \x1b[32me\x1b[0m to exit the rules.");
+    } else {
+        term.print_xy(x_pos, y_pos, "Press \x1b[32me\x1b[0m to exit the rules.");
+    }
+
+}
+
+/// scroll the rules or go back to the screen below them
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    let line = game.rules_line.min(get_last_start(game));
+    match key.code {
+        event::KeyCode::Char('e') | event::KeyCode::Esc | event::KeyCode::F(1) => {
+            game.rules_line = 0;
+            game.pop_gamestate();
+            return;
+        }
+        event::KeyCode::Up => game.rules_line = line.saturating_sub(1),
+        event::KeyCode::Down => game.rules_line = (line + 1).min(get_last_start(game)),
+        event::KeyCode::PageUp => game.rules_line = line.saturating_sub(get_page_len(game)),
+        event::KeyCode::PageDown => game.rules_line = (line + get_page_len(game)).min(get_last_start(game)),
+        _ => return,
+    }
+    game.update = true;
+
+}
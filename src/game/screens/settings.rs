@@ -0,0 +1,39 @@
+// part of the Minesweeper game for the terminal
+use crate::game::GAME_NAME;
+use crate::game::game_struct::Game;
+use crate::game::screens::on_off;
+use crate::term::Term;
+use crossterm::event;
+
+/// the settings for the next game
+pub fn draw(term: &mut Term, game: &Game) {
+
+    let settings = &game.settings;
+
+    term.cls();
+    term.print_xy(1, 1, format!("{} - Settings", GAME_NAME).as_str());
+
+    term.print_xy(1, 3, format!("\x1b[32mn\x1b[0m) The first move opens the neighbours too: \x1b[1m{}\x1b[0m", on_off(settings.safe_neighbours)).as_str());
+    term.print_xy(1, 4, format!("\x1b[32mg\x1b[0m) Only boards that can be solved without guessing: \x1b[1m{}\x1b[0m", on_off(settings.no_guess)).as_str());
+    term.print_xy(1, 5, format!("\x1b[32mf\x1b[0m) All mines must be flagged to win: \x1b[1m{}\x1b[0m", on_off(settings.flag_all)).as_str());
+
+    term.print_xy(1, 6, format!("\x1b[32mp\x1b[0m) Practice, a lost game can be undone, it is not counted: \x1b[1m{}\x1b[0m", on_off(settings.practice)).as_str());
+
+    term.print_xy(1, 8, "Press the key of a setting to switch it, ESC or ENTER to go back.");
+
+}
+
+/// switch the settings on and off
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    match key.code {
+        event::KeyCode::Char('n') => game.settings.safe_neighbours = !game.settings.safe_neighbours,
+        event::KeyCode::Char('g') => game.settings.no_guess = !game.settings.no_guess,
+        event::KeyCode::Char('f') => game.settings.flag_all = !game.settings.flag_all,
+        event::KeyCode::Char('p') => game.settings.practice = !game.settings.practice,
+        event::KeyCode::Esc | event::KeyCode::Enter => game.pop_gamestate(),
+        _ => return,
+    }
+    game.update = true;
+
+}
@@ -0,0 +1,84 @@
+// part of the Minesweeper game for the terminal
+use crate::game::GAME_NAME;
+use crate::game::board::BoardSize;
+use crate::game::game_struct::{Game,GameState};
+use crate::game::replay::Replay;
+use crate::game::savegame::SaveGame;
+use crate::game::screens::CustomBoardForm;
+use crate::term::Term;
+use crossterm::event;
+
+/// this is the screen for the game start, the user selects a board size here
+pub fn draw(term: &mut Term, _game: &Game) {
+
+    term.cls();
+    term.print_xy(1, 1, format!("Welcome to {} !", GAME_NAME).as_str());
+
+    term.print_xy(1, 3, "How difficult should be your minefield?");
+    term.print_xy(1, 5, "1) \x1b[32ms\x1b[0mmall minefield");
+    term.print_xy(1, 6, "2) \x1b[32mm\x1b[0medium minefield");
+    term.print_xy(1, 7, "3) \x1b[32ml\x1b[0marge minefield");
+    term.print_xy(1, 8, "4) \x1b[32mc\x1b[0mustom minefield");
+    if SaveGame::exists() {
+        term.print_xy(1, 9, "5) \x1b[32mr\x1b[0mesume the last game, a new game gives it up");
+    }
+
+    term.print_xy(1, 11, "Press \x1b[32mo\x1b[0m for the settings, \x1b[32mh\x1b[0m for the high scores, \x1b[32mt\x1b[0m for the statistics,");
+    if Replay::exists() {
+        term.print_xy(1, 12, "\x1b[32mv\x1b[0m for the replay of the last game, \x1b[32mF1\x1b[0m for the rules or \x1b[32mq\x1b[0m to quit.");
+    } else {
+        term.print_xy(1, 12, "\x1b[32mF1\x1b[0m for the rules or \x1b[32mq\x1b[0m to quit.");
+    }
+    term.print_xy(1, 13, "Select your size or press ENTER. The default difficulty is \x1b[1msmall\x1b[0m.");
+
+}
+
+/// select the board size and start the game
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    let board_size = match key.code {
+        event::KeyCode::Char('1') | event::KeyCode::Char('s') => BoardSize::Small,
+        event::KeyCode::Char('2') | event::KeyCode::Char('m') => BoardSize::Medium,
+        event::KeyCode::Char('3') | event::KeyCode::Char('l') => BoardSize::Large,
+        event::KeyCode::Enter => BoardSize::default(),
+        event::KeyCode::Char('4') | event::KeyCode::Char('c') => {
+            game.custom_form = CustomBoardForm::default();
+            game.push_gamestate(GameState::CustomBoard);
+            return;
+        }
+        event::KeyCode::Char('5') | event::KeyCode::Char('r') => {
+            game.resume_game();
+            return;
+        }
+        event::KeyCode::Char('o') => {
+            game.push_gamestate(GameState::Settings);
+            return;
+        }
+        event::KeyCode::Char('h') => {
+            game.score_page = 0;
+            game.push_gamestate(GameState::HighScores);
+            return;
+        }
+        event::KeyCode::Char('t') => {
+            game.push_gamestate(GameState::Statistics);
+            return;
+        }
+        event::KeyCode::Char('v') => {
+            game.start_playback();
+            return;
+        }
+        event::KeyCode::F(1) => {
+            game.push_gamestate(GameState::Rules);
+            return;
+        }
+        event::KeyCode::Char('q') | event::KeyCode::F(10) => {
+            game.set_gamestate(GameState::GiveUp);
+            return;
+        }
+        _ => return,
+    };
+
+    game.settings.board_size = board_size;
+    game.new_game();
+
+}
@@ -0,0 +1,57 @@
+// part of the Minesweeper game for the terminal
+use crate::game::GAME_NAME;
+use crate::game::game_struct::{format_seconds, Game};
+use crate::game::stats::SizeStats;
+use crate::term::Term;
+use crossterm::event;
+
+/// a line of the table, the first column is the board size
+fn format_line(board: &str, s: &SizeStats) -> String {
+    format!("{:<18} {:>6} {:>6} {:>6} {:>5.1}% {:>6} {:>6} {:>8} {:>9}",
+        board, s.played, s.won, s.lost, s.get_win_rate(), s.streak, s.longest_streak,
+        format_seconds(s.get_average_seconds()), s.revealed)
+}
+
+/// the lifetime statistics of all board sizes
+pub fn draw(term: &mut Term, game: &Game) {
+
+    term.cls();
+    term.print_xy(1, 1, format!("{} - Statistics", GAME_NAME).as_str());
+
+    let sizes = game.stats.get_sizes();
+    if sizes.is_empty() {
+        term.print_xy(1, 3, "There are no statistics yet, play a game first.");
+        term.print_xy(1, 5, "Press ESC or ENTER to go back.");
+        return;
+    }
+
+    let header = format!("{:<18} {:>6} {:>6} {:>6} {:>6} {:>6} {:>6} {:>8} {:>9}",
+        "Board", "Played", "Won", "Lost", "Rate", "Streak", "Best", "Avg time", "Revealed");
+    term.print_xy(1, 3, format!("\x1b[1m{}\x1b[0m", header).as_str());
+
+    // the board sizes, that don't fit into the terminal, are counted in the total only
+    let rows = (game.get_term_height() as usize).saturating_sub(8).max(1);
+    let mut y = 4;
+    for s in sizes.iter().take(rows) {
+        let board = format!("{}x{}, {} mines", s.width, s.height, s.mines);
+        term.print_xy(1, y, &format_line(&board, s));
+        y += 1;
+    }
+    term.print_xy(1, y, format!("\x1b[1m{}\x1b[0m", format_line("All boards", &game.stats.get_total())).as_str());
+    if sizes.len() > rows {
+        term.print_xy(1, y + 1, format!("{} more board sizes don't fit into the terminal.", sizes.len() - rows).as_str());
+    }
+
+    term.print_xy(1, y + 2, "The streaks of all boards are the best of every board size.");
+    term.print_xy(1, y + 3, "Press ESC or ENTER to go back.");
+
+}
+
+/// there is only the way back
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    if matches!(key.code, event::KeyCode::Esc | event::KeyCode::Enter) {
+        game.pop_gamestate();
+    }
+
+}
@@ -0,0 +1,31 @@
+// part of the Minesweeper game for the terminal
+use crate::game::game_struct::{Game, GameState};
+use crate::term::Term;
+use crossterm::event;
+
+/// this screen replaces all others, until the terminal is large enough
+pub fn draw(term: &mut Term, game: &Game) {
+
+    let (width, height) = game.get_min_term_size();
+    let lines = [
+        String::from("The terminal is too small."),
+        format!("Please resize it to {}x{}.", width, height),
+    ];
+
+    term.cls();
+    let y = (game.get_term_height() / 2).saturating_sub(1);
+    for (i, line) in lines.iter().enumerate() {
+        let x = game.get_term_width().saturating_sub(line.len() as u16) / 2;
+        term.print_xy(x, y + i as u16, line);
+    }
+
+}
+
+/// the hidden screen gets no keys, the game can only be left
+pub fn handle_key(game: &mut Game, key: &event::KeyEvent) {
+
+    if let event::KeyCode::Char('q') | event::KeyCode::F(10) = key.code {
+        game.set_gamestate(GameState::GiveUp);
+    }
+
+}
@@ -0,0 +1,182 @@
+// part of the Minesweeper game for the terminal
+use crate::game::board::{Board, BoardSize};
+use crate::game::storage::Values;
+
+/// the settings of a game, the player selects them on the start screen
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub struct Settings {
+    pub board_size: BoardSize,
+    pub safe_neighbours: bool,
+    pub no_guess: bool,
+    pub flag_all: bool,
+    pub practice: bool,
+    pub seed: Option<u64>,
+    pub start: Option<(usize, usize)>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            board_size: BoardSize::default(),
+            safe_neighbours: true,
+            no_guess: false,
+            flag_all: false,
+            practice: false,
+            seed: None,
+            start: None,
+        }
+    }
+}
+
+impl Settings {
+
+    /// read the command line arguments, returns an error message for invalid arguments
+    pub fn from_args(args: impl Iterator<Item = String>) -> Result<Settings, String> {
+        let mut settings = Settings::default();
+        let mut args = args.skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or("--seed needs a number")?;
+                    settings.read_seed_text(&value)?;
+                }
+                "-h" | "--help" => return Err(String::from(USAGE)),
+                _ => return Err(format!("unknown argument: {}\n{}", arg, USAGE)),
+            }
+        }
+        Ok(settings)
+    }
+
+    /// the seed with everything, that the layout of the bombs depends on: the seed, the board size,
+    /// the first picked cell and the rules, n for the safe neighbours and g for no guess, like 42-16x16x40-8,8-n.
+    /// Without a picked cell there are no bombs yet, the seed is enough
+    pub fn get_seed_text(&self, seed: u64, start: Option<(usize, usize)>) -> String {
+        let Some((x, y)) = start else { return seed.to_string(); };
+        let (width, height, mines) = self.board_size.get_dimension();
+        let mut text = format!("{}-{}x{}x{}-{},{}", seed, width, height, mines, x + 1, y + 1);
+        let rules: String = [(self.safe_neighbours, 'n'), (self.no_guess, 'g')].iter()
+            .filter(|(on, _)| *on).map(|(_, letter)| *letter).collect();
+        if !rules.is_empty() {
+            text.push('-');
+            text.push_str(&rules);
+        }
+        text
+    }
+
+    /// read a seed of get_seed_text, a seed with the size starts the game with these settings at once.
+    /// Returns an error message for an invalid seed
+    fn read_seed_text(&mut self, text: &str) -> Result<(), String> {
+        let invalid = || format!("invalid seed: {}", text);
+        let mut parts = text.split('-');
+        self.seed = Some(parts.next().and_then(|seed| seed.parse().ok()).ok_or_else(invalid)?);
+        let Some(size) = parts.next() else { return Ok(()); };
+        let values: Vec<u32> = size.split('x').map(|value| value.parse().map_err(|_| invalid())).collect::<Result<_, _>>()?;
+        let [width, height, mines] = values[..] else { return Err(invalid()); };
+        let width = u16::try_from(width).map_err(|_| invalid())?;
+        let height = u16::try_from(height).map_err(|_| invalid())?;
+        self.set_board_size(width, height, mines).map_err(|_| invalid())?;
+        let start = parts.next().and_then(|start| start.split_once(','))
+            .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)));
+        match start {
+            Some((x, y)) if x >= 1 && y >= 1 && x <= width as usize && y <= height as usize => self.start = Some((x - 1, y - 1)),
+            _ => return Err(invalid()),
+        }
+        let rules = parts.next().unwrap_or("");
+        if parts.next().is_some() || rules.chars().any(|letter| letter != 'n' && letter != 'g') {
+            return Err(invalid());
+        }
+        self.safe_neighbours = rules.contains('n');
+        self.no_guess = rules.contains('g');
+        Ok(())
+    }
+
+    /// set the size of the board, a preset is shown with its name on the start screen.
+    /// Returns an error message for an invalid size
+    fn set_board_size(&mut self, width: u16, height: u16, mines: u32) -> Result<(), String> {
+        self.board_size = BoardSize::Custom { width, height, mines };
+        if self.board_size.get_dimension() != (width as usize, height as usize, mines) {
+            return Err(String::from("invalid board size"));
+        }
+        for preset in [BoardSize::Small, BoardSize::Medium, BoardSize::Large] {
+            if preset.get_dimension() == self.board_size.get_dimension() {
+                self.board_size = preset;
+            }
+        }
+        Ok(())
+    }
+
+    /// create a board of this size with these rules, the seed is random
+    pub fn create_board(&self) -> Board {
+        let mut board = Board::new(self.board_size);
+        board.set_safe_neighbours(self.safe_neighbours);
+        board.set_no_guess(self.no_guess);
+        board.set_flag_all(self.flag_all);
+        board
+    }
+
+    /// the settings as lines of a file, the seed is not part of it
+    pub fn get_text(&self) -> String {
+        let (width, height, mines) = self.board_size.get_dimension();
+        format!("width {}\nheight {}\nmines {}\nsafe_neighbours {}\nno_guess {}\nflag_all {}\npractice {}\n",
+            width, height, mines, self.safe_neighbours as u8, self.no_guess as u8, self.flag_all as u8, self.practice as u8)
+    }
+
+    /// read the settings from the values of a file, returns an error message for invalid values
+    pub fn from_values(values: &Values) -> Result<Settings, String> {
+        let width: u16 = values.get("width")?;
+        let height: u16 = values.get("height")?;
+        let mines: u32 = values.get("mines")?;
+        let mut settings = Settings {
+            safe_neighbours: values.get::<u8>("safe_neighbours")? != 0,
+            no_guess: values.get::<u8>("no_guess")? != 0,
+            flag_all: values.get::<u8>("flag_all")? != 0,
+            // older files have no practice mode
+            practice: values.get::<u8>("practice").unwrap_or(0) != 0,
+            ..Settings::default()
+        };
+        settings.set_board_size(width, height, mines)?;
+        Ok(settings)
+    }
+
+}
+
+/// the help text for the command line
+const USAGE: &str = "Usage: minesweeper [--seed <seed>]
+
+  --seed <seed>   play the board of this seed again, the seed is shown at the end of a game.
+                  A seed like 42-16x16x40-8,8-n has the board size, the first picked cell
+                  and the rules too, the game starts at once with the same bombs";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// read the settings of a command line with the given seed
+    fn read_seed(seed: &str) -> Result<Settings, String> {
+        Settings::from_args(["minesweeper", "--seed", seed].iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn seed_text_round_trip() {
+        let settings = Settings { board_size: BoardSize::Custom { width: 20, height: 12, mines: 33 }, safe_neighbours: false, no_guess: true, ..Settings::default() };
+        let text = settings.get_seed_text(42, Some((19, 0)));
+        assert_eq!(text, "42-20x12x33-20,1-g");
+        let loaded = read_seed(&text).unwrap();
+        assert_eq!(loaded.get_seed_text(42, loaded.start), text);
+        assert_eq!(loaded.seed, Some(42));
+        assert_eq!(loaded.start, Some((19, 0)));
+        // a preset keeps its name
+        assert_eq!(read_seed("7-10x10x10-1,1-n").unwrap().board_size, BoardSize::Small);
+    }
+
+    #[test]
+    fn seed_without_a_start_is_the_number() {
+        assert_eq!(Settings::default().get_seed_text(42, None), "42");
+        let settings = read_seed("42").unwrap();
+        assert_eq!(settings.seed, Some(42));
+        assert_eq!(settings.start, None);
+        for seed in ["x", "42-9x9", "42-9x9x10", "42-9x9x10-10,1", "42-9x9x10-0,1", "42-9x9x10-1,1-x", "42-9x9x100-1,1"] {
+            assert!(read_seed(seed).is_err(), "{}", seed);
+        }
+    }
+}
@@ -0,0 +1,799 @@
+// part of the Minesweeper game for the terminal
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use crate::game::board::Board;
+use crate::game::cell::CellMarker;
+
+/// The most steps to enumerate the mines of a group of cells, a larger group is only estimated
+const MAX_STEPS: usize = 2_000_000;
+
+/// The most cells of a group to enumerate, the enumeration goes one cell deeper
+/// for every cell and keeps a count for every cell and every number of mines
+const MAX_GROUP_CELLS: usize = 1_000;
+
+/// What the solver knows about a cell
+#[derive(Copy,Clone,Debug,Default,PartialEq)]
+pub enum Knowledge {
+    #[default]
+    Covered,
+    Mine,
+    Open(u8),
+}
+
+/// The rule that was used to find a safe cell or a mine
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub enum Reason {
+    /// the number has all its mines, the other covered cells around are safe
+    Satisfied,
+    /// the number has as many covered cells around as mines left
+    AllMines,
+    /// two overlapping numbers, like the well known 1-2 pattern
+    Pattern,
+    /// the number of mines left on the whole board decides it
+    MineCount,
+    /// every possible layout of the mines agrees
+    AllLayouts,
+}
+
+/// A cell that is logically safe or a mine, the source is the number that proves it
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub struct Deduction {
+    pub x: usize,
+    pub y: usize,
+    pub is_mine: bool,
+    pub reason: Reason,
+    pub source: (usize, usize),
+}
+
+/// A cell the player should look at: a logically safe cell or a certain mine,
+/// without a reason it is the cell with the lowest chance to be a mine
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub struct Hint {
+    pub x: usize,
+    pub y: usize,
+    pub is_mine: bool,
+    pub reason: Option<Reason>,
+    pub source: (usize, usize),
+    pub chance: f64,
+}
+
+/// A number on the board with its covered cells around and the mines left
+struct Constraint {
+    x: usize,
+    y: usize,
+    cells: Vec<usize>,
+    mines: usize,
+}
+
+/// The possible mine layouts of a group of cells, that share numbers.
+/// For every count of mines: the weight of the layouts and how often every cell is a mine in them
+struct Layouts {
+    cells: Vec<usize>,
+    weights: Vec<f64>,
+    mines: Vec<Vec<f64>>,
+}
+
+/// The state of the enumeration of a group, every cell is a mine or not
+struct Enumeration {
+    cell_constraints: Vec<Vec<usize>>,
+    needed: Vec<isize>,
+    left: Vec<usize>,
+    is_mine: Vec<bool>,
+    mines: usize,
+    steps: usize,
+    layouts: Layouts,
+}
+
+impl Enumeration {
+
+    /// try both values of the cell and go on with the next cell,
+    /// returns FALSE if the group has too many layouts
+    fn run(&mut self, cell: usize) -> bool {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return false;
+        }
+        if cell == self.is_mine.len() {
+            // all numbers are satisfied, because no cell is left for them
+            self.layouts.weights[self.mines] += 1.0;
+            for (index, &is_mine) in self.is_mine.iter().enumerate() {
+                if is_mine {
+                    self.layouts.mines[self.mines][index] += 1.0;
+                }
+            }
+            return true;
+        }
+        for is_mine in [false, true] {
+            let mut valid = true;
+            for &c in &self.cell_constraints[cell] {
+                self.left[c] -= 1;
+                if is_mine { self.needed[c] -= 1; }
+                valid &= self.needed[c] >= 0 && self.needed[c] as usize <= self.left[c];
+            }
+            if valid {
+                self.is_mine[cell] = is_mine;
+                if is_mine { self.mines += 1; }
+                let finished = self.run(cell + 1);
+                if is_mine { self.mines -= 1; }
+                self.is_mine[cell] = false;
+                if !finished {
+                    return false;
+                }
+            }
+            for &c in &self.cell_constraints[cell] {
+                self.left[c] += 1;
+                if is_mine { self.needed[c] += 1; }
+            }
+        }
+        true
+    }
+
+}
+
+/// Convolve two distributions of mine counts
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+/// The collected deductions, every cell is added only once
+struct Deductions {
+    width: usize,
+    list: Vec<Deduction>,
+    seen: Vec<bool>,
+}
+
+impl Deductions {
+
+    fn new(width: usize, cells: usize) -> Deductions {
+        Deductions { width, list: Vec::new(), seen: vec![false; cells] }
+    }
+
+    fn add(&mut self, cells: &[usize], is_mine: bool, reason: Reason, source: &Constraint) {
+        for &index in cells {
+            if !self.seen[index] {
+                self.seen[index] = true;
+                self.list.push(Deduction {
+                    x: index % self.width,
+                    y: index / self.width,
+                    is_mine,
+                    reason,
+                    source: (source.x, source.y),
+                });
+            }
+        }
+    }
+
+}
+
+/// A deterministic solver, it never guesses and knows only the cells that are revealed
+#[derive(Clone,Debug)]
+pub struct Solver {
+    width: usize,
+    height: usize,
+    mines: u32,
+    cells: Vec<Knowledge>,
+}
+
+impl Solver {
+
+    /// Creates a solver for a board where all cells are covered
+    pub fn new(width: usize, height: usize, mines: u32) -> Solver {
+        Solver {
+            width,
+            height,
+            mines,
+            cells: vec![Knowledge::Covered; width * height],
+        }
+    }
+
+    /// Creates a solver with the knowledge of the player, only the revealed numbers are used,
+    /// because a flag of the player can be wrong
+    pub fn from_board(board: &Board) -> Solver {
+        let width = board.get_width() as usize;
+        let height = board.get_height() as usize;
+        let mut solver = Solver::new(width, height, board.get_max_bombs());
+        for y in 0..height {
+            for x in 0..width {
+                let cell = board.get_cell(x, y);
+                if cell.is_revealed() && cell.is_empty() {
+                    solver.set(x, y, Knowledge::Open(cell.get_bombs_around()));
+                }
+            }
+        }
+        solver
+    }
+
+    /// Get the knowledge about a cell
+    pub fn get(&self, x: usize, y: usize) -> Knowledge {
+        self.cells[y * self.width + x]
+    }
+
+    /// Set the knowledge about a cell
+    pub fn set(&mut self, x: usize, y: usize, knowledge: Knowledge) {
+        self.cells[y * self.width + x] = knowledge;
+    }
+
+    /// Get all valid cells around the given cell
+    fn neighbours(&self, x_pos: usize, y_pos: usize) -> Vec<(usize, usize)> {
+        let mut cells = Vec::with_capacity(8);
+        for y in y_pos.saturating_sub(1)..=(y_pos + 1).min(self.height - 1) {
+            for x in x_pos.saturating_sub(1)..=(x_pos + 1).min(self.width - 1) {
+                if x == x_pos && y == y_pos { continue; }
+                cells.push((x, y));
+            }
+        }
+        cells
+    }
+
+    /// Collect all revealed numbers, that have covered cells around
+    fn constraints(&self) -> Vec<Constraint> {
+        let mut constraints = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let Knowledge::Open(number) = self.get(x, y) else { continue; };
+                let mut cells = Vec::new();
+                let mut mines_found = 0;
+                for (nx, ny) in self.neighbours(x, y) {
+                    match self.get(nx, ny) {
+                        Knowledge::Covered => cells.push(ny * self.width + nx),
+                        Knowledge::Mine => mines_found += 1,
+                        Knowledge::Open(_) => {}
+                    }
+                }
+                if !cells.is_empty() {
+                    let mines = (number as usize).saturating_sub(mines_found);
+                    constraints.push(Constraint { x, y, cells, mines });
+                }
+            }
+        }
+        constraints
+    }
+
+    /// Find all cells that are logically safe or mines with the current knowledge,
+    /// the simple rules are tried first, an empty vector means a guess is needed
+    pub fn deduce(&self) -> Vec<Deduction> {
+        let constraints = self.constraints();
+        let mut found = Deductions::new(self.width, self.cells.len());
+
+        // a single number tells everything about its cells
+        for c in &constraints {
+            if c.mines == 0 {
+                found.add(&c.cells, false, Reason::Satisfied, c);
+            } else if c.mines == c.cells.len() {
+                found.add(&c.cells, true, Reason::AllMines, c);
+            }
+        }
+        if !found.list.is_empty() {
+            return found.list;
+        }
+
+        // two numbers share some cells
+        let by_pos: HashMap<(usize, usize), usize> = constraints.iter()
+            .enumerate()
+            .map(|(i, c)| ((c.x, c.y), i))
+            .collect();
+        for a in &constraints {
+            for y in a.y.saturating_sub(2)..=(a.y + 2).min(self.height - 1) {
+                for x in a.x.saturating_sub(2)..=(a.x + 2).min(self.width - 1) {
+                    if x == a.x && y == a.y { continue; }
+                    let Some(&i) = by_pos.get(&(x, y)) else { continue; };
+                    let b = &constraints[i];
+                    let only_a: Vec<usize> = a.cells.iter().filter(|c| !b.cells.contains(c)).copied().collect();
+                    let only_b: Vec<usize> = b.cells.iter().filter(|c| !a.cells.contains(c)).copied().collect();
+                    if only_a.len() == a.cells.len() { continue; }
+                    if !only_b.is_empty() && b.mines >= a.mines && b.mines - a.mines == only_b.len() {
+                        // the mines of b, that don't fit into a, fill all cells of b alone
+                        found.add(&only_b, true, Reason::Pattern, b);
+                        found.add(&only_a, false, Reason::Pattern, b);
+                    } else if only_a.is_empty() && !only_b.is_empty() && a.mines == b.mines {
+                        // all mines of b are in the cells shared with a
+                        found.add(&only_b, false, Reason::Pattern, b);
+                    }
+                }
+            }
+        }
+        if !found.list.is_empty() {
+            return found.list;
+        }
+
+        // the mine counter is known to the player too
+        let covered: Vec<usize> = (0..self.cells.len()).filter(|&i| self.cells[i] == Knowledge::Covered).collect();
+        let mines_found = self.cells.iter().filter(|&&k| k == Knowledge::Mine).count();
+        let mines_left = (self.mines as usize).saturating_sub(mines_found);
+        let counter = Constraint { x: 0, y: 0, cells: covered, mines: mines_left };
+        if !counter.cells.is_empty() {
+            if counter.mines == 0 {
+                found.add(&counter.cells, false, Reason::MineCount, &counter);
+            } else if counter.mines == counter.cells.len() {
+                found.add(&counter.cells, true, Reason::MineCount, &counter);
+            }
+        }
+        found.list
+    }
+
+    /// Split the numbers into groups, that share covered cells. The cells of a group
+    /// are in the order they are found, so the neighbours of a cell follow soon
+    fn groups(constraints: &[Constraint]) -> Vec<(Vec<usize>, Vec<usize>)> {
+        let mut by_cell: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, c) in constraints.iter().enumerate() {
+            for &cell in &c.cells {
+                by_cell.entry(cell).or_default().push(i);
+            }
+        }
+        let mut groups = Vec::new();
+        let mut done = vec![false; constraints.len()];
+        let mut seen = HashSet::new();
+        for start in 0..constraints.len() {
+            if done[start] { continue; }
+            done[start] = true;
+            let mut group_constraints = vec![start];
+            let mut group_cells = Vec::new();
+            let mut next = 0;
+            while next < group_constraints.len() {
+                for &cell in &constraints[group_constraints[next]].cells {
+                    if !seen.insert(cell) { continue; }
+                    group_cells.push(cell);
+                    for &i in &by_cell[&cell] {
+                        if !done[i] {
+                            done[i] = true;
+                            group_constraints.push(i);
+                        }
+                    }
+                }
+                next += 1;
+            }
+            groups.push((group_cells, group_constraints));
+        }
+        groups
+    }
+
+    /// Find all mine layouts of a group, returns None if there are too many to count them
+    fn enumerate(constraints: &[Constraint], cells: Vec<usize>, group: &[usize]) -> Option<Layouts> {
+        if cells.len() > MAX_GROUP_CELLS {
+            return None;
+        }
+        let local: HashMap<usize, usize> = cells.iter().enumerate().map(|(i, &cell)| (cell, i)).collect();
+        let mut cell_constraints = vec![Vec::new(); cells.len()];
+        for (i, &c) in group.iter().enumerate() {
+            for cell in &constraints[c].cells {
+                cell_constraints[local[cell]].push(i);
+            }
+        }
+        let mut enumeration = Enumeration {
+            cell_constraints,
+            needed: group.iter().map(|&c| constraints[c].mines as isize).collect(),
+            left: group.iter().map(|&c| constraints[c].cells.len()).collect(),
+            is_mine: vec![false; cells.len()],
+            mines: 0,
+            steps: 0,
+            layouts: Layouts {
+                weights: vec![0.0; cells.len() + 1],
+                mines: vec![vec![0.0; cells.len()]; cells.len() + 1],
+                cells,
+            },
+        };
+        if !enumeration.run(0) {
+            return None;
+        }
+        // the weights are scaled, so the large groups can't overflow
+        let mut layouts = enumeration.layouts;
+        let total: f64 = layouts.weights.iter().sum();
+        if total == 0.0 {
+            return None;
+        }
+        for (weight, mines) in layouts.weights.iter_mut().zip(layouts.mines.iter_mut()) {
+            *weight /= total;
+            mines.iter_mut().for_each(|m| *m /= total);
+        }
+        Some(layouts)
+    }
+
+    /// Calculate the exact chance of every covered cell to be a mine, all layouts of the mines,
+    /// that fit to the numbers and to the mines left, are equally likely.
+    /// If there are too many layouts to count them, the chances are estimated.
+    /// The known mines and the open cells have no chance.
+    /// Returns the chances and TRUE if they are exact
+    pub fn calculate_chances(&self) -> (Vec<f64>, bool) {
+        match self.count_chances() {
+            Some(chances) => (chances, true),
+            None => (self.estimate_chances(), false),
+        }
+    }
+
+    /// Count the layouts of the mines, the cells away from the numbers share the mines left
+    fn count_chances(&self) -> Option<Vec<f64>> {
+        let constraints = self.constraints();
+        let mut all_layouts = Vec::new();
+        for (cells, group) in Solver::groups(&constraints) {
+            all_layouts.push(Solver::enumerate(&constraints, cells, &group)?);
+        }
+
+        let mines_found = self.cells.iter().filter(|&&k| k == Knowledge::Mine).count();
+        let mines_left = (self.mines as usize).saturating_sub(mines_found);
+        let frontier: usize = all_layouts.iter().map(|l| l.cells.len()).sum();
+        let covered = self.cells.iter().filter(|&&k| k == Knowledge::Covered).count();
+        let others = covered - frontier;
+
+        // the number of layouts of the other cells for every count of mines in the groups,
+        // as logarithm and scaled to the largest one, it can be a very large number
+        let mut ln_others = vec![f64::NEG_INFINITY; frontier + 1];
+        let mut ln_binomial = 0.0;
+        for r in 0..=mines_left.min(others) {
+            if r > 0 {
+                ln_binomial += ((others - r + 1) as f64).ln() - (r as f64).ln();
+            }
+            if r + frontier >= mines_left {
+                ln_others[mines_left - r] = ln_binomial;
+            }
+        }
+        let max = ln_others.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if max == f64::NEG_INFINITY {
+            return None;
+        }
+        let others_weights: Vec<f64> = ln_others.iter().map(|&ln| (ln - max).exp()).collect();
+
+        // the other groups for every group: all groups before it and all groups after it
+        let mut before = vec![vec![1.0]];
+        for layouts in &all_layouts {
+            before.push(convolve(&before[before.len() - 1], &layouts.weights));
+        }
+        let mut after = vec![vec![1.0]];
+        for layouts in all_layouts.iter().rev() {
+            after.push(convolve(&after[after.len() - 1], &layouts.weights));
+        }
+        after.reverse();
+
+        let all = &before[all_layouts.len()];
+        let total: f64 = all.iter().zip(&others_weights).map(|(w, o)| w * o).sum();
+        if total <= 0.0 || !total.is_finite() {
+            return None;
+        }
+
+        let mut chances = vec![0.0; self.cells.len()];
+        for (g, layouts) in all_layouts.iter().enumerate() {
+            let rest = convolve(&before[g], &after[g + 1]);
+            for (k, mines) in layouts.mines.iter().enumerate() {
+                let factor: f64 = rest.iter().enumerate().map(|(j, r)| r * others_weights[k + j]).sum();
+                for (index, m) in mines.iter().enumerate() {
+                    chances[layouts.cells[index]] += m * factor / total;
+                }
+            }
+        }
+        if others > 0 {
+            let expected: f64 = all.iter().zip(&others_weights).enumerate()
+                .map(|(k, (w, o))| w * o * mines_left.saturating_sub(k) as f64)
+                .sum();
+            let chance = expected / total / others as f64;
+            let mut in_group = vec![false; self.cells.len()];
+            for &cell in all_layouts.iter().flat_map(|l| &l.cells) {
+                in_group[cell] = true;
+            }
+            for (index, knowledge) in self.cells.iter().enumerate() {
+                if *knowledge == Knowledge::Covered && !in_group[index] {
+                    chances[index] = chance;
+                }
+            }
+        }
+        for (index, chance) in chances.iter_mut().enumerate() {
+            match self.cells[index] {
+                Knowledge::Covered => *chance = chance.clamp(0.0, 1.0),
+                Knowledge::Mine => *chance = 1.0,
+                Knowledge::Open(_) => *chance = 0.0,
+            }
+        }
+        Some(chances)
+    }
+
+    /// Estimate the chance of every covered cell to be a mine, a number around it gives
+    /// the highest chance of its cells, the other cells share the mines left.
+    /// The known mines and the open cells have no chance
+    fn estimate_chances(&self) -> Vec<f64> {
+        let constraints = self.constraints();
+        let mut chances = vec![f64::NAN; self.cells.len()];
+        let mut frontier_mines = 0.0;
+        for c in &constraints {
+            let chance = c.mines as f64 / c.cells.len() as f64;
+            for &index in &c.cells {
+                if chances[index].is_nan() || chance > chances[index] {
+                    chances[index] = chance;
+                }
+            }
+        }
+        for (index, chance) in chances.iter().enumerate() {
+            if !chance.is_nan() && self.cells[index] == Knowledge::Covered {
+                frontier_mines += chance;
+            }
+        }
+        let mines_found = self.cells.iter().filter(|&&k| k == Knowledge::Mine).count();
+        let mines_left = (self.mines as usize).saturating_sub(mines_found) as f64;
+        let others: Vec<usize> = (0..self.cells.len())
+            .filter(|&i| self.cells[i] == Knowledge::Covered && chances[i].is_nan())
+            .collect();
+        for &index in &others {
+            chances[index] = ((mines_left - frontier_mines) / others.len() as f64).clamp(0.0, 1.0);
+        }
+        for (index, chance) in chances.iter_mut().enumerate() {
+            match self.cells[index] {
+                Knowledge::Covered => {}
+                Knowledge::Mine => *chance = 1.0,
+                Knowledge::Open(_) => *chance = 0.0,
+            }
+        }
+        chances
+    }
+
+    /// Find a hint for the player: a safe cell is the best, a mine the player has not flagged
+    /// the second best. Without a deduction the cell with the lowest chance is a guess,
+    /// unless the layouts were counted and none of them has a mine in it.
+    /// Returns None if there is no covered cell
+    pub fn find_hint(board: &Board) -> Option<Hint> {
+        let mut solver = Solver::from_board(board);
+        loop {
+            let deductions = solver.deduce();
+            if deductions.is_empty() {
+                break;
+            }
+            if let Some(d) = deductions.iter().find(|d| !d.is_mine) {
+                return Some(Hint { x: d.x, y: d.y, is_mine: false, reason: Some(d.reason), source: d.source, chance: 0.0 });
+            }
+            if let Some(d) = deductions.iter().find(|d| board.get_cell(d.x, d.y).get_state() != CellMarker::HasBomb) {
+                return Some(Hint { x: d.x, y: d.y, is_mine: true, reason: Some(d.reason), source: d.source, chance: 1.0 });
+            }
+            // all found mines are flagged already, the next deductions need them
+            for d in deductions {
+                solver.set(d.x, d.y, Knowledge::Mine);
+            }
+        }
+        let (chances, exact) = solver.calculate_chances();
+        // a flagged cell is only a guess, if all covered cells are flagged
+        let covered: Vec<usize> = (0..chances.len()).filter(|&i| solver.cells[i] == Knowledge::Covered).collect();
+        let unflagged: Vec<usize> = covered.iter()
+            .copied()
+            .filter(|&i| board.get_cell(i % solver.width, i / solver.width).get_state() != CellMarker::HasBomb)
+            .collect();
+        let candidates = if unflagged.is_empty() { covered } else { unflagged };
+        let (index, chance) = candidates.iter()
+            .map(|&i| (i, chances[i]))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        let (x, y) = (index % solver.width, index / solver.width);
+        let reason = if exact && chance == 0.0 { Some(Reason::AllLayouts) } else { None };
+        Some(Hint { x, y, is_mine: false, reason, source: (x, y), chance })
+    }
+
+    /// Open a cell with the content of the board, empty cells open their neighbours too.
+    /// Returns the number of opened cells
+    fn open(&mut self, board: &Board, x_pos: usize, y_pos: usize) -> usize {
+        let mut opened = 0;
+        let mut stack = vec![(x_pos, y_pos)];
+        while let Some((x, y)) = stack.pop() {
+            if self.get(x, y) != Knowledge::Covered { continue; }
+            let bombs_around = board.get_cell(x, y).get_bombs_around();
+            self.set(x, y, Knowledge::Open(bombs_around));
+            opened += 1;
+            if bombs_around == 0 {
+                stack.extend(self.neighbours(x, y));
+            }
+        }
+        opened
+    }
+
+}
+
+/// The result of a game, that the solver played alone
+#[derive(Copy,Clone,Debug,Default,PartialEq)]
+pub struct Autoplay {
+    pub won: bool,
+    pub guesses: u32,
+    pub moves: u32,
+}
+
+/// Play a board without a player: all deductions first, then the cell with the lowest chance
+/// to be a mine is a guess. The first pick is not counted as a guess, it is always safe.
+/// The solver flags only certain mines, so its flags are known mines
+pub fn autoplay(board: &mut Board, start_x: usize, start_y: usize) -> Autoplay {
+    let width = board.get_width() as usize;
+    let mut result = Autoplay::default();
+    let mut picks = vec![(start_x, start_y)];
+    loop {
+        for (x, y) in picks.drain(..) {
+            result.moves += 1;
+            if board.pick_cell(x, y).is_exploded() {
+                return result;
+            }
+        }
+        if board.check_win_condition() {
+            result.won = true;
+            return result;
+        }
+
+        let mut solver = Solver::from_board(board);
+        for index in 0..solver.cells.len() {
+            if board.get_cell(index % width, index / width).get_state() == CellMarker::HasBomb {
+                solver.cells[index] = Knowledge::Mine;
+            }
+        }
+        // the found mines are flagged, the next deductions need them
+        let mut flagged = false;
+        loop {
+            let deductions = solver.deduce();
+            if deductions.is_empty() {
+                break;
+            }
+            for d in deductions {
+                if d.is_mine {
+                    solver.set(d.x, d.y, Knowledge::Mine);
+                    board.toggle_marker(d.x, d.y, CellMarker::HasBomb);
+                    flagged = true;
+                } else {
+                    picks.push((d.x, d.y));
+                }
+            }
+            if !picks.is_empty() {
+                break;
+            }
+        }
+        if !picks.is_empty() || flagged {
+            continue;
+        }
+
+        // no deduction, the cell with the lowest chance is the guess
+        let (chances, exact) = solver.calculate_chances();
+        let Some((index, chance)) = (0..chances.len())
+            .filter(|&i| solver.cells[i] == Knowledge::Covered)
+            .map(|i| (i, chances[i]))
+            .min_by(|a, b| a.1.total_cmp(&b.1)) else { return result; };
+        // an estimated chance of 0 is not sure, the pick is a guess too
+        if chance > 0.0 || !exact {
+            result.guesses += 1;
+        }
+        picks.push((index % width, index / width));
+    }
+}
+
+/// Check if the board can be cleared from the given cell without any guess,
+/// after the deadline the check gives up and the board counts as not solvable
+pub fn is_solvable(board: &Board, start_x: usize, start_y: usize, deadline: Instant) -> bool {
+    let width = board.get_width() as usize;
+    let height = board.get_height() as usize;
+    let mut solver = Solver::new(width, height, board.get_max_bombs());
+    let mut covered_safe = width * height - board.get_max_bombs() as usize;
+    if board.get_cell(start_x, start_y).is_bomb() {
+        return false;
+    }
+    covered_safe -= solver.open(board, start_x, start_y);
+    while covered_safe > 0 {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        let deductions = solver.deduce();
+        if deductions.is_empty() {
+            return false;
+        }
+        for d in deductions {
+            if d.is_mine {
+                solver.set(d.x, d.y, Knowledge::Mine);
+            } else {
+                covered_safe -= solver.open(board, d.x, d.y);
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::BoardSize;
+    use std::time::Duration;
+
+    /// a solver from rows of text: a dot is covered, a star is a known mine, a digit is open
+    fn create_solver(rows: &[&str], mines: u32) -> Solver {
+        let mut solver = Solver::new(rows[0].len(), rows.len(), mines);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                match c {
+                    '*' => solver.set(x, y, Knowledge::Mine),
+                    '0'..='8' => solver.set(x, y, Knowledge::Open(c as u8 - b'0')),
+                    _ => {}
+                }
+            }
+        }
+        solver
+    }
+
+    /// the deductions as cells and if they are mines, sorted by the position
+    fn deduce(solver: &Solver) -> Vec<(usize, usize, bool, Reason)> {
+        let mut found: Vec<_> = solver.deduce().iter().map(|d| (d.x, d.y, d.is_mine, d.reason)).collect();
+        found.sort_by_key(|&(x, y, _, _)| (y, x));
+        found
+    }
+
+    #[test]
+    fn satisfied_number_clears_its_cells() {
+        let solver = create_solver(&["...", "*1."], 3);
+        assert_eq!(deduce(&solver), vec![
+            (0, 0, false, Reason::Satisfied),
+            (1, 0, false, Reason::Satisfied),
+            (2, 0, false, Reason::Satisfied),
+            (2, 1, false, Reason::Satisfied),
+        ]);
+    }
+
+    #[test]
+    fn number_with_only_mines_around() {
+        let solver = create_solver(&["..", "3."], 3);
+        assert_eq!(deduce(&solver), vec![
+            (0, 0, true, Reason::AllMines),
+            (1, 0, true, Reason::AllMines),
+            (1, 1, true, Reason::AllMines),
+        ]);
+    }
+
+    #[test]
+    fn pattern_1_2_2_1() {
+        let solver = create_solver(&["....", "1221"], 2);
+        assert_eq!(deduce(&solver), vec![
+            (1, 0, true, Reason::Pattern),
+            (2, 0, true, Reason::Pattern),
+        ]);
+    }
+
+    #[test]
+    fn mine_counter_decides() {
+        let solver = create_solver(&["....", "...."], 8);
+        let found = deduce(&solver);
+        assert_eq!(found.len(), 8);
+        assert!(found.iter().all(|&(_, _, is_mine, reason)| is_mine && reason == Reason::MineCount));
+        let solver = create_solver(&["*...", "...."], 1);
+        let found = deduce(&solver);
+        assert_eq!(found.len(), 7);
+        assert!(found.iter().all(|&(_, _, is_mine, reason)| !is_mine && reason == Reason::MineCount));
+    }
+
+    #[test]
+    fn no_deduction_without_a_number() {
+        let solver = create_solver(&["...", "..."], 3);
+        assert!(solver.deduce().is_empty());
+    }
+
+    #[test]
+    fn no_guess_board_is_solvable() {
+        for _ in 0..10 {
+            let mut board = Board::new(BoardSize::Medium);
+            board.set_no_guess(true);
+            board.pick_cell(10, 10);
+            assert!(is_solvable(&board, 10, 10, Instant::now() + Duration::from_secs(60)));
+        }
+    }
+
+    #[test]
+    fn chances_sum_to_the_mines() {
+        for seed in 0..20 {
+            let mut board = Board::new(BoardSize::Custom { width: 16, height: 16, mines: 40 });
+            board.set_seed(seed);
+            board.pick_cell(8, 8);
+            let (chances, exact) = Solver::from_board(&board).calculate_chances();
+            assert!(exact);
+            let sum: f64 = chances.iter().sum();
+            assert!((sum - 40.0).abs() < 1e-6, "seed {}: {}", seed, sum);
+        }
+    }
+
+    #[test]
+    fn chances_of_a_known_pattern() {
+        // the 1 has its mine in one of two cells, the three cells away from the numbers share the other mine
+        let solver = create_solver(&["...", "1..", "0.."], 2);
+        let (chances, exact) = solver.calculate_chances();
+        assert!(exact);
+        let expected = [0.5, 0.5, 1.0 / 3.0, 0.0, 0.0, 1.0 / 3.0, 0.0, 0.0, 1.0 / 3.0];
+        for (chance, expected) in chances.iter().zip(expected) {
+            assert!((chance - expected).abs() < 1e-9, "{:?}", chances);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// part of the Minesweeper game for the terminal
+use crate::game::storage;
+
+/// the file of the statistics in the data directory
+const STATS_FILE: &str = "stats.txt";
+
+/// the first line of the file explains the columns
+const HEADER: &str = "# minesweeper statistics: width height mines played won lost streak longest_streak won_seconds revealed";
+
+/// the end of a game, that is counted in the statistics
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub enum GameResult {
+    Won,
+    Lost,
+    GaveUp,
+}
+
+/// the statistics of all games on one board size,
+/// a game that was given up counts as played, but not as won or lost
+#[derive(Clone,Debug,Default,PartialEq)]
+pub struct SizeStats {
+    pub width: u16,
+    pub height: u16,
+    pub mines: u32,
+    pub played: u64,
+    pub won: u64,
+    pub lost: u64,
+    pub streak: u64,
+    pub longest_streak: u64,
+    pub won_seconds: u64,
+    pub revealed: u64,
+}
+
+impl SizeStats {
+
+    /// the statistics of a board size without any game
+    fn new(width: u16, height: u16, mines: u32) -> SizeStats {
+        SizeStats { width, height, mines, ..SizeStats::default() }
+    }
+
+    /// check if the statistics belong to a board of the given size
+    pub fn is_size(&self, width: u16, height: u16, mines: u32) -> bool {
+        self.width == width && self.height == height && self.mines == mines
+    }
+
+    /// the won games in percent of the played games
+    pub fn get_win_rate(&self) -> f64 {
+        if self.played == 0 { 0.0 } else { self.won as f64 * 100.0 / self.played as f64 }
+    }
+
+    /// the average time of the won games
+    pub fn get_average_seconds(&self) -> u64 {
+        self.won_seconds.checked_div(self.won).unwrap_or(0)
+    }
+
+    /// add the numbers of an other board size, the streaks are the best of both
+    fn add(&mut self, other: &SizeStats) {
+        self.played += other.played;
+        self.won += other.won;
+        self.lost += other.lost;
+        self.streak = self.streak.max(other.streak);
+        self.longest_streak = self.longest_streak.max(other.longest_streak);
+        self.won_seconds += other.won_seconds;
+        self.revealed += other.revealed;
+    }
+
+    /// a line of the file, the columns are separated by tabs
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.width, self.height, self.mines, self.played, self.won, self.lost,
+            self.streak, self.longest_streak, self.won_seconds, self.revealed)
+    }
+
+    /// read a line of the file, returns None for invalid lines
+    fn from_line(line: &str) -> Option<SizeStats> {
+        let mut columns = line.split('\t');
+        Some(SizeStats {
+            width: columns.next()?.parse().ok()?,
+            height: columns.next()?.parse().ok()?,
+            mines: columns.next()?.parse().ok()?,
+            played: columns.next()?.parse().ok()?,
+            won: columns.next()?.parse().ok()?,
+            lost: columns.next()?.parse().ok()?,
+            streak: columns.next()?.parse().ok()?,
+            longest_streak: columns.next()?.parse().ok()?,
+            won_seconds: columns.next()?.parse().ok()?,
+            revealed: columns.next()?.parse().ok()?,
+        })
+    }
+
+}
+
+/// the lifetime statistics of all board sizes
+#[derive(Clone,Debug,Default)]
+pub struct Stats {
+    sizes: Vec<SizeStats>,
+}
+
+impl Stats {
+
+    /// read the statistics from the data directory, invalid lines are skipped
+    pub fn load() -> Stats {
+        let text = storage::read(STATS_FILE).ok().flatten().unwrap_or_default();
+        let mut stats = Stats::default();
+        for line in text.lines() {
+            if line.starts_with('#') { continue; }
+            if let Some(size_stats) = SizeStats::from_line(line) {
+                stats.sizes.push(size_stats);
+            }
+        }
+        stats.sizes.sort_by_key(|s| (s.width as u32 * s.height as u32, s.mines, s.width));
+        stats
+    }
+
+    /// write the statistics to the data directory
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+        for size_stats in &self.sizes {
+            text.push_str(&size_stats.to_line());
+            text.push('\n');
+        }
+        storage::write(STATS_FILE, &text)
+    }
+
+    /// get the statistics of all board sizes, the smallest board comes first
+    pub fn get_sizes(&self) -> &[SizeStats] {
+        &self.sizes
+    }
+
+    /// get the statistics of a board size
+    pub fn get_size(&self, width: u16, height: u16, mines: u32) -> Option<&SizeStats> {
+        self.sizes.iter().find(|s| s.is_size(width, height, mines))
+    }
+
+    /// the sum of all board sizes
+    pub fn get_total(&self) -> SizeStats {
+        let mut total = SizeStats::default();
+        for size_stats in &self.sizes {
+            total.add(size_stats);
+        }
+        total
+    }
+
+    /// count a finished game, a lost or given up game ends the streak
+    pub fn record(&mut self, width: u16, height: u16, mines: u32, result: GameResult, seconds: u64, revealed: u64) {
+        let pos = match self.sizes.iter().position(|s| s.is_size(width, height, mines)) {
+            Some(pos) => pos,
+            None => {
+                self.sizes.push(SizeStats::new(width, height, mines));
+                self.sizes.sort_by_key(|s| (s.width as u32 * s.height as u32, s.mines, s.width));
+                self.sizes.iter().position(|s| s.is_size(width, height, mines)).unwrap_or(0)
+            }
+        };
+        let size_stats = &mut self.sizes[pos];
+        size_stats.played += 1;
+        size_stats.revealed += revealed;
+        match result {
+            GameResult::Won => {
+                size_stats.won += 1;
+                size_stats.won_seconds += seconds;
+                size_stats.streak += 1;
+                size_stats.longest_streak = size_stats.longest_streak.max(size_stats.streak);
+            }
+            GameResult::Lost => {
+                size_stats.lost += 1;
+                size_stats.streak = 0;
+            }
+            GameResult::GaveUp => size_stats.streak = 0,
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_counts_the_streaks() {
+        let mut stats = Stats::default();
+        for result in [GameResult::Won, GameResult::Won, GameResult::Won, GameResult::Lost, GameResult::Won] {
+            stats.record(10, 10, 10, result, 30, 50);
+        }
+        let s = stats.get_size(10, 10, 10).unwrap();
+        assert_eq!((s.played, s.won, s.lost), (5, 4, 1));
+        assert_eq!((s.streak, s.longest_streak), (1, 3));
+        assert_eq!(s.get_average_seconds(), 30);
+        assert_eq!(s.revealed, 250);
+        // a given up game ends the streak, but it is not lost
+        stats.record(10, 10, 10, GameResult::GaveUp, 0, 0);
+        let s = stats.get_size(10, 10, 10).unwrap();
+        assert_eq!((s.played, s.lost, s.streak, s.longest_streak), (6, 1, 0, 3));
+    }
+
+    #[test]
+    fn total_has_the_best_streaks() {
+        let mut stats = Stats::default();
+        stats.record(20, 20, 40, GameResult::Won, 100, 360);
+        stats.record(10, 10, 10, GameResult::Won, 20, 90);
+        stats.record(10, 10, 10, GameResult::Won, 40, 90);
+        // the smallest board comes first
+        assert_eq!(stats.get_sizes()[0].width, 10);
+        let total = stats.get_total();
+        assert_eq!((total.played, total.won, total.streak, total.longest_streak), (3, 3, 2, 2));
+        assert_eq!(total.get_average_seconds(), 53);
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let mut stats = Stats::default();
+        stats.record(30, 16, 99, GameResult::Lost, 12, 34);
+        let s = &stats.get_sizes()[0];
+        assert_eq!(SizeStats::from_line(&s.to_line()).as_ref(), Some(s));
+    }
+}
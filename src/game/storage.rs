@@ -0,0 +1,84 @@
+// part of the Minesweeper game for the terminal
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// the directory of the game below the data directory of the user
+const DATA_DIR: &str = "minesweeper";
+
+/// the data directory follows the XDG specification,
+/// it is $XDG_DATA_HOME/minesweeper or ~/.local/share/minesweeper
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local").join("share"),
+    };
+    Some(base.join(DATA_DIR))
+}
+
+/// read a file from the data directory, a missing file is not an error
+pub fn read(file_name: &str) -> io::Result<Option<String>> {
+    let Some(dir) = data_dir() else { return Ok(None); };
+    match fs::read_to_string(dir.join(file_name)) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// write a file to the data directory, the directory is created if needed
+pub fn write(file_name: &str, text: &str) -> io::Result<()> {
+    let dir = data_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    fs::create_dir_all(&dir)?;
+    // write to a temporary file first, so a crash never leaves a broken file
+    let tmp = dir.join(format!("{}.tmp", file_name));
+    fs::write(&tmp, text)?;
+    fs::rename(tmp, dir.join(file_name))
+}
+
+/// check if a file exists in the data directory
+pub fn exists(file_name: &str) -> bool {
+    data_dir().is_some_and(|dir| dir.join(file_name).is_file())
+}
+
+/// remove a file from the data directory, a missing file is not an error
+pub fn remove(file_name: &str) -> io::Result<()> {
+    let Some(dir) = data_dir() else { return Ok(()); };
+    match fs::remove_file(dir.join(file_name)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// the values at the beginning of a file, every line is a key and a value separated by a space
+pub struct Values<'a> {
+    values: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Values<'a> {
+
+    /// read the values until the given line, returns an error message for an invalid line
+    pub fn read(lines: &mut impl Iterator<Item = &'a str>, end: &str) -> Result<Values<'a>, String> {
+        let mut values = HashMap::new();
+        for line in lines.by_ref() {
+            if line == end { break; }
+            let (key, value) = line.split_once(' ').ok_or_else(|| format!("invalid line: {}", line))?;
+            values.insert(key, value);
+        }
+        Ok(Values { values })
+    }
+
+    /// get the text of a value
+    pub fn get_str(&self, key: &str) -> Result<&'a str, String> {
+        self.values.get(key).copied().ok_or_else(|| format!("missing value: {}", key))
+    }
+
+    /// get a value as a number or any other type, that can be parsed
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        let value = self.get_str(key)?;
+        value.parse::<T>().map_err(|_| format!("invalid value of {}: {}", key, value))
+    }
+
+}
@@ -0,0 +1,167 @@
+// part of the Minesweeper game for the terminal
+use crate::game::dimension::Dimension;
+use crate::game::position::Position;
+
+/// The viewport is the visible part of the game board,
+/// if the board is larger than the terminal only this window is drawn
+#[derive(Copy,Clone,Debug,Default,PartialEq)]
+pub struct Viewport {
+    pos: Position,
+    size: Dimension,
+    board: Dimension,
+}
+
+impl Viewport {
+
+    /// Creates a new viewport in the upper left corner of the board,
+    /// the viewport is never larger than the board itself
+    pub fn new(board_width: u16, board_height: u16, max_width: u16, max_height: u16) -> Viewport {
+        Viewport {
+            pos: Position::new(0, 0),
+            size: Dimension::new(board_width.min(max_width), board_height.min(max_height)),
+            board: Dimension::new(board_width, board_height),
+        }
+    }
+
+    /// Get the first visible column of the board
+    pub fn get_x(&self) -> u16 {
+        self.pos.get_x()
+    }
+
+    /// Get the first visible row of the board
+    pub fn get_y(&self) -> u16 {
+        self.pos.get_y()
+    }
+
+    /// Get the number of visible columns
+    pub fn get_width(&self) -> u16 {
+        self.size.get_width()
+    }
+
+    /// Get the number of visible rows
+    pub fn get_height(&self) -> u16 {
+        self.size.get_height()
+    }
+
+    /// Check if the board is wider than the viewport
+    pub fn is_scrollable_x(&self) -> bool {
+        self.board.get_width() > self.size.get_width()
+    }
+
+    /// Check if the board is higher than the viewport
+    pub fn is_scrollable_y(&self) -> bool {
+        self.board.get_height() > self.size.get_height()
+    }
+
+    /// Move the viewport by the given number of cells, it stops at the board edges,
+    /// returns TRUE if the viewport was moved
+    pub fn scroll(&mut self, dx: i32, dy: i32) -> bool {
+        let max_x = (self.board.get_width() - self.size.get_width()) as i32;
+        let max_y = (self.board.get_height() - self.size.get_height()) as i32;
+        let x = (self.pos.get_x() as i32 + dx).clamp(0, max_x) as u16;
+        let y = (self.pos.get_y() as i32 + dy).clamp(0, max_y) as u16;
+        let moved = (x, y) != self.pos.get();
+        self.pos.set(x, y);
+        moved
+    }
+
+    /// Move the viewport as little as possible, so that the given cell of the board is visible,
+    /// returns TRUE if the viewport was moved
+    pub fn follow(&mut self, x: u16, y: u16) -> bool {
+        let dx = Viewport::distance(x, self.pos.get_x(), self.size.get_width());
+        let dy = Viewport::distance(y, self.pos.get_y(), self.size.get_height());
+        self.scroll(dx, dy)
+    }
+
+    /// The distance of a cell to the visible range start..start+len
+    fn distance(cell: u16, start: u16, len: u16) -> i32 {
+        let end = start as i32 + len as i32 - 1;
+        if (cell as i32) < start as i32 {
+            cell as i32 - start as i32
+        } else if cell as i32 > end {
+            cell as i32 - end
+        } else {
+            0
+        }
+    }
+
+    /// Check if the position relative to the upper left corner of the viewport is visible
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.size.get_width() as i32 && y < self.size.get_height() as i32
+    }
+
+    /// Get the start and the length of the horizontal scrollbar thumb
+    pub fn get_thumb_x(&self) -> (u16, u16) {
+        Viewport::thumb(self.pos.get_x(), self.size.get_width(), self.board.get_width())
+    }
+
+    /// Get the start and the length of the vertical scrollbar thumb
+    pub fn get_thumb_y(&self) -> (u16, u16) {
+        Viewport::thumb(self.pos.get_y(), self.size.get_height(), self.board.get_height())
+    }
+
+    /// The track of a scrollbar has the length of the viewport,
+    /// the thumb shows the visible part of the board on it
+    fn thumb(pos: u16, size: u16, total: u16) -> (u16, u16) {
+        if total == 0 || size == 0 {
+            return (0, 0);
+        }
+        let track = size as u32;
+        let len = (track * size as u32 / total as u32).max(1);
+        let mut start = track * pos as u32 / total as u32;
+        // the thumb must reach the end of the track, if the end of the board is visible
+        if pos as u32 + size as u32 >= total as u32 {
+            start = track - len;
+        }
+        (start.min(track - len) as u16, len as u16)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_stops_at_the_edges() {
+        let mut view = Viewport::new(100, 50, 40, 20);
+        assert_eq!((view.get_width(), view.get_height()), (40, 20));
+        assert!(!view.scroll(-1, -1));
+        assert!(view.scroll(1000, 1000));
+        assert_eq!((view.get_x(), view.get_y()), (60, 30));
+        assert!(!view.scroll(1, 1));
+    }
+
+    #[test]
+    fn small_board_is_not_scrollable() {
+        let view = Viewport::new(9, 9, 40, 20);
+        assert_eq!((view.get_width(), view.get_height()), (9, 9));
+        assert!(!view.is_scrollable_x() && !view.is_scrollable_y());
+        assert_eq!(view.get_thumb_x(), (0, 9));
+    }
+
+    #[test]
+    fn follow_moves_as_little_as_possible() {
+        let mut view = Viewport::new(100, 50, 40, 20);
+        assert!(!view.follow(39, 19));
+        assert!(view.follow(45, 10));
+        assert_eq!((view.get_x(), view.get_y()), (6, 0));
+        assert!(view.follow(0, 25));
+        assert_eq!((view.get_x(), view.get_y()), (0, 6));
+        assert!(view.follow(99, 49));
+        assert_eq!((view.get_x(), view.get_y()), (60, 30));
+    }
+
+    #[test]
+    fn thumb_shows_the_visible_part() {
+        let mut view = Viewport::new(100, 50, 40, 20);
+        assert_eq!(view.get_thumb_x(), (0, 16));
+        assert_eq!(view.get_thumb_y(), (0, 8));
+        view.scroll(30, 10);
+        assert_eq!(view.get_thumb_x(), (12, 16));
+        // at the end of the board the thumb reaches the end of the track
+        view.scroll(1000, 1000);
+        assert_eq!(view.get_thumb_x(), (24, 16));
+        assert_eq!(view.get_thumb_y(), (12, 8));
+    }
+}
@@ -0,0 +1,23 @@
+//! Minesweeper game
+//! for the terminal
+//!
+//! Copyright (c) 2025 Markus Müller
+//!
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+//! The engine of the game: a board with its cells, the moves on it and a solver.
+//! The terminal frontend is behind the default feature `terminal`, without it
+//! the library has no dependency on crossterm.
+pub mod game;
+#[cfg(feature = "terminal")]
+mod term;
+
+pub use game::board::{Board, BoardSize, GameState, Outcome, RevealResult};
+pub use game::cell::{Cell, CellMarker};
+pub use game::position::Position;
@@ -11,9 +11,6 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 //
-mod game;
-mod term;
-
 fn main() -> Result<(), i32> {
-    crate::game::run()
+    minesweeper::game::run()
 }
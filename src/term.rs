@@ -1,80 +1,106 @@
 // part of the Minesweeper game for the terminal
-use crossterm::{cursor, event, style, terminal, tty::IsTty, ExecutableCommand, QueueableCommand};
+mod screen;
+
+use crate::term::screen::Screen;
+use crossterm::{cursor, event, style, terminal, ExecutableCommand, QueueableCommand};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
 use std::io::{stdout, Stdout, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+/// The terminal of the game, it is switched to raw mode on the alternate screen while it exists.
+/// If it is dropped, even while a panic unwinds, the terminal of the user is restored.
+/// A frame is drawn into a screen buffer, `update()` prints only its differences to the last frame.
 pub struct Term {
     stdout: Stdout,
-    width: u16,
-    height: u16,
+    screen: Screen,
+    last_screen: Screen,
+    cursor_shown: bool,
+    quit: Arc<AtomicBool>,
+}
+
+impl Drop for Term {
+    fn drop(&mut self) {
+        Term::restore();
+    }
 }
 
-#[allow(unused)]
 impl Term {
 
-    pub fn new() -> Term {
-        let (w,h) = terminal::size().unwrap_or_else(|err| {
-            eprintln!("Error: {}", err);
-            (0,0)
-        });
-        Term {
+    /// Set up the terminal for the game: raw mode, alternate screen, mouse and focus events
+    /// and a hidden cursor. The signals to terminate the game are caught, see `is_quit_requested()`
+    pub fn new() -> std::io::Result<Term> {
+        let mut term = Term {
             stdout: stdout(),
-            width: w,
-            height: h,
+            // the first cls() clears the terminal, the screens get their size there
+            screen: Screen::new(0, 0),
+            last_screen: Screen::new(0, 0),
+            cursor_shown: false,
+            quit: Arc::new(AtomicBool::new(false)),
+        };
+        Term::set_panic_hook();
+        for signal in [SIGHUP, SIGINT, SIGTERM] {
+            signal_hook::flag::register(signal, Arc::clone(&term.quit))?;
         }
-    }
-
-    pub fn is_tty(&self) -> bool {
-        self.stdout.is_tty()
-    }
-
-    pub fn enable_raw_mode(&self) {
-        terminal::enable_raw_mode().unwrap_or_else(|err| {
-            eprintln!("Error: {}", err);
-            std::process::exit(1);
-        });
-    }
-
-    pub fn disable_raw_mode(&self) {
-        terminal::disable_raw_mode().unwrap_or_else(|err| {
-            eprintln!("Error: {}", err);
-            std::process::exit(1);
-        });
-    }
-
-    pub fn enable_mouse_events(&mut self) {
-        self.stdout.execute(event::EnableMouseCapture).unwrap_or_else(|err| {
-            eprintln!("Error: {}", err);
-            std::process::exit(1);
-        });
-    }
-
-    pub fn disable_mouse_events(&mut self) {
-        self.stdout.execute(event::DisableMouseCapture).unwrap();
-    }
-
-    pub fn enable_focus_events(&mut self) {
-        self.stdout.execute(event::EnableFocusChange).unwrap_or_else(|err| {
-            eprintln!("Error: {}", err);
-            std::process::exit(1);
-        });
-    }
-
-    pub fn disable_focus_events(&mut self) {
-        self.stdout.execute(event::DisableFocusChange).unwrap();
+        // if one of these fails, term is dropped and everything is restored
+        term.stdout.execute(terminal::EnterAlternateScreen)?;
+        terminal::enable_raw_mode()?;
+        term.stdout.execute(event::EnableFocusChange)?;
+        term.stdout.execute(event::EnableMouseCapture)?;
+        term.stdout.execute(cursor::Hide)?;
+        term.cls();
+        Ok(term)
+    }
+
+    /// Restore the terminal of the user, all errors are ignored here,
+    /// because it's called on the way out in any case
+    pub fn restore() {
+        let mut stdout = stdout();
+        let _ = stdout.execute(cursor::Show);
+        let _ = stdout.execute(event::DisableMouseCapture);
+        let _ = stdout.execute(event::DisableFocusChange);
+        let _ = stdout.execute(terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+
+    /// Restore the terminal before the panic message is printed, otherwise it gets lost
+    /// on the alternate screen
+    fn set_panic_hook() {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            Term::restore();
+            default_hook(info);
+        }));
+    }
+
+    /// Check if the game was asked to quit by a signal like SIGTERM
+    pub fn is_quit_requested(&self) -> bool {
+        self.quit.load(Ordering::Relaxed)
     }
 
     pub fn hide_cursor(&mut self) {
+        self.cursor_shown = false;
         self.stdout.execute(cursor::Hide).unwrap();
     }
 
+    /// The cursor is shown with the next update, at the position of the last print or move
     pub fn show_cursor(&mut self) {
-        self.stdout.execute(cursor::Show).unwrap();
+        self.cursor_shown = true;
     }
 
+    /// Print the changes of the frame to the terminal, the frame is the last frame after it
     pub fn update(&mut self) {
+        let changes = self.screen.get_changes(&self.last_screen);
+        self.stdout.queue(style::Print(changes)).unwrap();
+        if self.cursor_shown {
+            let (x, y) = self.screen.get_cursor();
+            self.stdout.queue(cursor::MoveTo(x, y)).unwrap();
+            self.stdout.queue(cursor::Show).unwrap();
+        }
         self.stdout.flush().unwrap_or_else(|err| {
             eprintln!("Error: {}", err);
         });
+        self.last_screen.clone_from(&self.screen);
     }
 
     pub fn get_size(&self) -> (u16,u16) {
@@ -85,29 +111,29 @@ impl Term {
         (w,h)
     }
 
-    pub fn get_width(&self) -> u16 {
-        self.width
-    }
-
-    pub fn get_height(&self) -> u16 {
-        self.height
-    }
-
+    /// Clear the frame, every frame begins with it. If the terminal was resized,
+    /// the terminal is cleared too and the next update prints the whole frame
     pub fn cls(&mut self) {
-        self.stdout.queue(terminal::Clear(terminal::ClearType::All)).unwrap();
+        let (w,h) = self.get_size();
+        if (w,h) != self.screen.get_size() {
+            self.screen = Screen::new(w, h);
+            self.last_screen = Screen::new(w, h);
+            self.stdout.queue(terminal::Clear(terminal::ClearType::All)).unwrap();
+        }
+        self.screen.clear();
     }
 
     pub fn clear_line(&mut self, line_no: u16) {
         self.move_xy(1, line_no);
-        self.stdout.queue(terminal::Clear(terminal::ClearType::CurrentLine)).unwrap();
+        self.screen.clear_line(line_no);
     }
 
     pub fn print(&mut self, text: &str) {
-        self.stdout.queue(style::Print(text)).unwrap();
+        self.screen.print(text);
     }
 
     pub fn move_xy(&mut self, x: u16, y: u16) {
-        self.stdout.queue(cursor::MoveTo(x, y)).unwrap();
+        self.screen.move_to(x, y);
     }
 
     pub fn print_xy(&mut self, x: u16, y: u16, text: &str) {
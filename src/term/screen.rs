@@ -0,0 +1,293 @@
+// part of the Minesweeper game for the terminal
+
+/// The colors and attributes of a cell on the screen, the colors are indexes of the 256 color palette
+#[derive(Copy,Clone,Debug,Default,PartialEq)]
+pub struct Style {
+    foreground: Option<u8>,
+    background: Option<u8>,
+    bold: bool,
+    reverse: bool,
+}
+
+impl Style {
+
+    /// Change the style with the parameters of a SGR sequence like `\x1b[97;100m`,
+    /// the unknown parameters are ignored
+    pub fn apply_sgr(&mut self, params: &str) {
+        let codes: Vec<u16> = params.split(';').map(|code| code.parse().unwrap_or(0)).collect();
+        let mut i = 0;
+        while i < codes.len() {
+            match codes[i] {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                7 => self.reverse = true,
+                27 => self.reverse = false,
+                code @ 30..=37 => self.foreground = Some((code - 30) as u8),
+                code @ 90..=97 => self.foreground = Some((code - 90 + 8) as u8),
+                39 => self.foreground = None,
+                code @ 40..=47 => self.background = Some((code - 40) as u8),
+                code @ 100..=107 => self.background = Some((code - 100 + 8) as u8),
+                49 => self.background = None,
+                code @ (38 | 48) if codes.get(i + 1) == Some(&5) => {
+                    let color = codes.get(i + 2).map(|&color| color.min(255) as u8);
+                    if code == 38 { self.foreground = color; } else { self.background = color; }
+                    i += 2;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Get the SGR sequence to change the terminal from a style to this style,
+    /// if something is switched off, the terminal is reset first. Without the old style
+    /// the terminal is reset too
+    pub fn get_sgr(&self, from: Option<Style>) -> String {
+        let reset = match from {
+            None => true,
+            Some(from) => (from.bold && !self.bold) || (from.reverse && !self.reverse)
+                || (from.foreground.is_some() && self.foreground.is_none())
+                || (from.background.is_some() && self.background.is_none()),
+        };
+        let from = if reset { Style::default() } else { from.unwrap_or_default() };
+        let mut params = Vec::new();
+        if reset { params.push(String::from("0")); }
+        if self.bold && !from.bold { params.push(String::from("1")); }
+        if self.reverse && !from.reverse { params.push(String::from("7")); }
+        if let Some(color) = self.foreground && self.foreground != from.foreground {
+            params.push(Style::get_color_code(color, 30));
+        }
+        if let Some(color) = self.background && self.background != from.background {
+            params.push(Style::get_color_code(color, 40));
+        }
+        if params.is_empty() {
+            return String::new();
+        }
+        format!("\x1b[{}m", params.join(";"))
+    }
+
+    /// The parameter of a color, the first 16 colors have their own codes
+    fn get_color_code(color: u8, base: u8) -> String {
+        match color {
+            0..8 => (base + color).to_string(),
+            8..16 => (base + 60 + color - 8).to_string(),
+            _ => format!("{};5;{}", base + 8, color),
+        }
+    }
+
+}
+
+/// A cell of the screen with its character and style
+#[derive(Copy,Clone,Debug,PartialEq)]
+pub struct ScreenCell {
+    symbol: char,
+    style: Style,
+}
+
+impl ScreenCell {
+
+    /// An empty cell with the default colors
+    const BLANK: ScreenCell = ScreenCell { symbol: ' ', style: Style { foreground: None, background: None, bold: false, reverse: false } };
+
+}
+
+/// The content of the terminal, one frame is drawn here first. The differences to the last frame
+/// are printed to the terminal, so the unchanged cells don't flicker
+#[derive(Clone,Debug)]
+pub struct Screen {
+    width: u16,
+    height: u16,
+    cells: Vec<ScreenCell>,
+    x: u16,
+    y: u16,
+    style: Style,
+}
+
+impl Screen {
+
+    /// A blank screen of the given size
+    pub fn new(width: u16, height: u16) -> Screen {
+        Screen {
+            width,
+            height,
+            cells: vec![ScreenCell::BLANK; width as usize * height as usize],
+            x: 0,
+            y: 0,
+            style: Style::default(),
+        }
+    }
+
+    pub fn get_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// The position, where the next text is printed
+    pub fn get_cursor(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    /// Clear all cells, the style of the text isn't changed
+    pub fn clear(&mut self) {
+        self.cells.fill(ScreenCell::BLANK);
+    }
+
+    /// Clear one line
+    pub fn clear_line(&mut self, y: u16) {
+        if y < self.height {
+            let start = y as usize * self.width as usize;
+            self.cells[start..start + self.width as usize].fill(ScreenCell::BLANK);
+        }
+    }
+
+    pub fn move_to(&mut self, x: u16, y: u16) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Print a text at the cursor, the SGR sequences change the style like in a terminal.
+    /// The text is cut at the right edge
+    pub fn print(&mut self, text: &str) {
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                self.read_escape(&mut chars);
+                continue;
+            }
+            if c.is_control() {
+                continue;
+            }
+            if self.x < self.width && self.y < self.height {
+                let index = self.y as usize * self.width as usize + self.x as usize;
+                self.cells[index] = ScreenCell { symbol: c, style: self.style };
+            }
+            self.x = self.x.saturating_add(1);
+        }
+    }
+
+    /// Read an escape sequence, only the SGR sequences are used
+    fn read_escape(&mut self, chars: &mut std::str::Chars) {
+        if chars.next() != Some('[') {
+            return;
+        }
+        let mut params = String::new();
+        for c in chars.by_ref() {
+            if ('\x40'..='\x7e').contains(&c) {
+                if c == 'm' {
+                    self.style.apply_sgr(&params);
+                }
+                return;
+            }
+            params.push(c);
+        }
+    }
+
+    /// Get the text to change the terminal from the last frame to this frame.
+    /// Only the changed cells are printed, the cursor jumps over the unchanged cells
+    /// and the style changes only if it's different
+    pub fn get_changes(&self, last: &Screen) -> String {
+        let mut text = String::new();
+        let mut cursor: Option<(u16, u16)> = None;
+        let mut style: Option<Style> = None;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = y as usize * self.width as usize + x as usize;
+                let cell = self.cells[index];
+                if last.cells[index] == cell {
+                    continue;
+                }
+                match cursor {
+                    Some((cx, cy)) if (cx, cy) == (x, y) => {}
+                    // a short gap is printed again, if it has the same style, that is shorter than a jump
+                    Some((cx, cy)) if cy == y && x - cx < 4 => {
+                        let gap = &self.cells[index - (x - cx) as usize..index];
+                        if gap.iter().all(|gap_cell| Some(gap_cell.style) == style) {
+                            text.extend(gap.iter().map(|gap_cell| gap_cell.symbol));
+                        } else {
+                            text.push_str(&format!("\x1b[{}C", x - cx));
+                        }
+                    }
+                    _ => text.push_str(&format!("\x1b[{};{}H", y + 1, x + 1)),
+                }
+                if style != Some(cell.style) {
+                    text.push_str(&cell.style.get_sgr(style));
+                    style = Some(cell.style);
+                }
+                text.push(cell.symbol);
+                // after the last column the position of the terminal cursor is not sure
+                cursor = if x + 1 < self.width { Some((x + 1, y)) } else { None };
+            }
+        }
+        if style.is_some_and(|style| style != Style::default()) {
+            text.push_str("\x1b[0m");
+        }
+        text
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a screen with two lines of text, as the last frame
+    fn create_screen() -> Screen {
+        let mut screen = Screen::new(12, 3);
+        screen.move_to(0, 0);
+        screen.print("Minesweeper");
+        screen.move_to(2, 1);
+        screen.print("\x1b[97;100m■■■■\x1b[0m");
+        screen
+    }
+
+    #[test]
+    fn unchanged_screen_prints_nothing() {
+        let screen = create_screen();
+        assert_eq!(screen.get_changes(&screen.clone()), "");
+    }
+
+    #[test]
+    fn only_changed_cells_are_printed() {
+        let last = create_screen();
+        let mut screen = last.clone();
+        screen.move_to(4, 0);
+        screen.print("S");
+        screen.move_to(9, 0);
+        screen.print("ER");
+        // the style of the terminal is not known, it is reset before the first cell
+        assert_eq!(screen.get_changes(&last), "\x1b[1;5H\x1b[0mS\x1b[1;10HER");
+    }
+
+    #[test]
+    fn short_gap_is_printed_again() {
+        let last = create_screen();
+        let mut screen = last.clone();
+        screen.move_to(0, 0);
+        screen.print("m");
+        screen.move_to(3, 0);
+        screen.print("E");
+        // the unchanged "in" between them is shorter than a jump
+        assert_eq!(screen.get_changes(&last), "\x1b[1;1H\x1b[0mminE");
+    }
+
+    #[test]
+    fn style_changes_with_the_cell() {
+        let last = create_screen();
+        let mut screen = last.clone();
+        screen.move_to(3, 1);
+        screen.print("\x1b[91;100m⚑\x1b[0m");
+        screen.move_to(0, 2);
+        screen.print("x");
+        assert_eq!(screen.get_changes(&last), "\x1b[2;4H\x1b[0;91;100m⚑\x1b[3;1H\x1b[0mx");
+    }
+
+    #[test]
+    fn sgr_sequences_set_the_style() {
+        let mut style = Style::default();
+        style.apply_sgr("1;38;5;208;44");
+        assert_eq!(style, Style { foreground: Some(208), background: Some(4), bold: true, reverse: false });
+        assert_eq!(style.get_sgr(Some(Style::default())), "\x1b[1;38;5;208;44m");
+        style.apply_sgr("0");
+        assert_eq!(style, Style::default());
+    }
+}
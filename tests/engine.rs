@@ -0,0 +1,132 @@
+// part of the Minesweeper game for the terminal
+// the game engine with the public API only, like a bot or another frontend uses it
+use minesweeper::{Board, BoardSize, CellMarker, GameState, Position, RevealResult};
+
+/// a board of 3x3 cells with a bomb in the top left and in the bottom right corner
+fn create_board() -> Board {
+    let mut board = Board::new(BoardSize::Custom { width: 3, height: 3, mines: 2 });
+    assert!(board.set_bombs(&[0, 8]));
+    board
+}
+
+#[test]
+fn first_reveal_is_safe() {
+    for seed in 0..50 {
+        let mut board = Board::new(BoardSize::Small);
+        board.set_seed(seed);
+        assert_eq!(board.get_state(), GameState::Ready);
+        let outcome = board.reveal(4, 4);
+        assert!(!outcome.is_exploded());
+        assert_eq!(board.get_cell(4, 4).get_bombs_around(), 0);
+        assert_eq!(board.get_start(), Some((4, 4)));
+    }
+}
+
+#[test]
+fn same_seed_creates_same_board() {
+    let mut first = Board::new(BoardSize::Medium);
+    let mut second = Board::new(BoardSize::Medium);
+    first.set_seed(42);
+    second.set_seed(42);
+    first.reveal(3, 7);
+    second.reveal(3, 7);
+    assert_eq!(first.get_bombs(), second.get_bombs());
+}
+
+#[test]
+fn reveal_opens_the_empty_cells() {
+    let mut board = create_board();
+    let outcome = board.reveal(2, 0);
+    assert_eq!(outcome.state, GameState::Running);
+    assert_eq!(outcome.changed.len(), 4);
+    assert_eq!(outcome.changed[0], Position::new(2, 0));
+    for position in [(1, 0), (1, 1), (2, 1)] {
+        assert!(outcome.changed.contains(&Position::new(position.0, position.1)));
+    }
+}
+
+#[test]
+fn outcome_has_the_changes_of_one_move() {
+    let mut board = create_board();
+    board.reveal(2, 0);
+    let outcome = board.flag(0, 0);
+    assert_eq!(outcome.changed, vec![Position::new(0, 0)]);
+    assert_eq!(board.get_cell(0, 0).get_state(), CellMarker::HasBomb);
+    assert_eq!(board.get_bombs_left(), 1);
+    let outcome = board.flag(0, 0);
+    assert_eq!(outcome.changed, vec![Position::new(0, 0)]);
+    assert_eq!(board.get_cell(0, 0).get_state(), CellMarker::None);
+}
+
+#[test]
+fn chord_opens_the_cells_without_flags() {
+    let mut board = create_board();
+    board.reveal(2, 0);
+    board.flag(0, 0);
+    let outcome = board.chord(1, 0);
+    assert_eq!(outcome.changed, vec![Position::new(0, 1)]);
+    // a reveal on a number chords it too
+    let outcome = board.reveal(1, 1);
+    assert!(outcome.changed.is_empty());
+}
+
+#[test]
+fn chord_with_a_wrong_flag_explodes() {
+    let mut board = create_board();
+    board.reveal(2, 0);
+    board.flag(0, 1);
+    let outcome = board.chord(1, 0);
+    assert!(outcome.is_exploded());
+    assert_eq!(board.get_state(), GameState::Lost);
+}
+
+#[test]
+fn revealing_all_empty_cells_wins() {
+    let mut board = create_board();
+    for (x, y) in [(2, 0), (0, 1), (0, 2), (1, 2)] {
+        board.reveal(x, y);
+    }
+    assert_eq!(board.get_state(), GameState::Won);
+    assert_eq!(board.count_revealed(), 7);
+}
+
+#[test]
+fn pick_cell_tells_why_nothing_opened() {
+    let mut board = create_board();
+    assert_eq!(board.pick_cell(3, 0), RevealResult::OutOfBounds);
+    board.flag(1, 2);
+    assert_eq!(board.pick_cell(1, 2), RevealResult::Flagged);
+    assert!(matches!(board.pick_cell(2, 0), RevealResult::Opened(_)));
+    assert_eq!(board.pick_cell(2, 0), RevealResult::AlreadyRevealed);
+    assert_eq!(board.pick_cell(2, 2), RevealResult::Exploded);
+}
+
+#[test]
+fn mark_switches_flag_and_question_mark() {
+    let mut board = create_board();
+    let markers: Vec<CellMarker> = (0..3).map(|_| {
+        board.mark(1, 2);
+        board.get_cell(1, 2).get_state()
+    }).collect();
+    assert_eq!(markers, [CellMarker::HasBomb, CellMarker::GuessBomb, CellMarker::None]);
+    board.question(1, 2);
+    assert_eq!(board.get_cell(1, 2).get_state(), CellMarker::GuessBomb);
+    // a revealed cell can't be marked
+    board.reveal(2, 0);
+    assert!(board.mark(2, 0).changed.is_empty());
+}
+
+#[test]
+fn bombs_are_placed_only_once() {
+    let mut board = create_board();
+    board.reveal(2, 0);
+    assert!(!board.set_bombs(&[1, 7]));
+    assert_eq!(board.get_bombs(), vec![0, 8]);
+    assert_eq!(board.count_revealed(), 4);
+}
+
+#[test]
+#[should_panic]
+fn get_cell_outside_of_the_board_panics() {
+    create_board().get_cell(3, 0);
+}